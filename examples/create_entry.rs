@@ -2,6 +2,7 @@ use hatena_blog_api::Client;
 use hatena_blog_api::Config;
 use hatena_blog_api::Entry;
 use hatena_blog_api::EntryParams;
+use hatena_blog_api::EntryStatus;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -14,7 +15,7 @@ async fn main() -> anyhow::Result<()> {
             "content1".to_string(),
             "2021-06-20T15:49:00+09:00".to_string(),
             vec![],
-            true,
+            EntryStatus::Draft,
         ))
         .await?;
     let entry: Entry = response.try_into()?;
@@ -3,13 +3,18 @@ use std::convert::TryInto;
 use hatena_blog_api::Client;
 use hatena_blog_api::Config;
 use hatena_blog_api::EntryId;
+use hatena_blog_api::PageCursor;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let config = Config::new_from_env()?;
     let client = Client::new(&config);
-    let response = client.list_entries_in_page(None).await?;
-    let partial_list: (Option<String>, Vec<EntryId>) = response.try_into()?;
+    let page = std::env::args()
+        .nth(1)
+        .map(|page| page.parse::<PageCursor>())
+        .transpose()?;
+    let response = client.list_entries_in_page(page.as_ref()).await?;
+    let partial_list: (Option<PageCursor>, Vec<EntryId>) = response.try_into()?;
     println!("{:?}", partial_list);
     Ok(())
 }
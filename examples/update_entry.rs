@@ -7,6 +7,7 @@ use hatena_blog_api::Config;
 use hatena_blog_api::Entry;
 use hatena_blog_api::EntryId;
 use hatena_blog_api::EntryParams;
+use hatena_blog_api::EntryStatus;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -22,7 +23,7 @@ async fn main() -> anyhow::Result<()> {
                 "content2".to_string(),
                 "2021-06-20T15:49:00+09:00".to_string(),
                 vec![],
-                true,
+                EntryStatus::Draft,
             ),
         )
         .await?;
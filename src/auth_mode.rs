@@ -0,0 +1,125 @@
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use sha1::Digest as _;
+use sha1::Sha1;
+use thiserror::Error;
+
+use crate::OAuthCredentials;
+
+/// How `Client` authenticates its requests.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum AuthMode {
+    /// HTTP Basic authentication (`Authorization: Basic ...`).
+    #[default]
+    Basic,
+    /// WSSE UsernameToken authentication (`X-WSSE: UsernameToken ...`).
+    /// The API key itself is never sent.
+    Wsse,
+    /// OAuth 1.0a (`Authorization: OAuth ...`) signed with the given credentials.
+    /// `Config::api_key` is not used.
+    OAuth(OAuthCredentials),
+}
+
+#[derive(Debug, Eq, Error, PartialEq)]
+#[error("auth mode parse error")]
+pub struct AuthModeParseError {
+    _private: (),
+}
+
+impl std::fmt::Display for AuthMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                AuthMode::Basic => "basic",
+                AuthMode::Wsse => "wsse",
+                AuthMode::OAuth(_) => "oauth",
+            }
+        )
+    }
+}
+
+/// Parses `basic` and `wsse`.
+///
+/// `AuthMode::OAuth` is displayed as `oauth`, but `oauth` is not parsed because it
+/// needs `OAuthCredentials`. Use `Config::new_with_oauth` instead.
+impl std::str::FromStr for AuthMode {
+    type Err = AuthModeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "basic" => Ok(AuthMode::Basic),
+            "wsse" => Ok(AuthMode::Wsse),
+            _ => Err(AuthModeParseError { _private: () }),
+        }
+    }
+}
+
+// PasswordDigest = Base64(SHA1(Nonce + Created + Password))
+pub(crate) fn wsse_header(username: &str, password: &str, nonce: &[u8], created: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(nonce);
+    hasher.update(created.as_bytes());
+    hasher.update(password.as_bytes());
+    let digest = STANDARD.encode(hasher.finalize());
+    format!(
+        r#"UsernameToken Username="{}", PasswordDigest="{}", Nonce="{}", Created="{}""#,
+        username,
+        digest,
+        STANDARD.encode(nonce),
+        created
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn string_conversion_test() {
+        assert_eq!(AuthMode::from_str("basic"), Ok(AuthMode::Basic));
+        assert_eq!(AuthMode::from_str("wsse"), Ok(AuthMode::Wsse));
+        assert_eq!(
+            AuthMode::from_str("WSSE"),
+            Err(AuthModeParseError { _private: () })
+        );
+        assert_eq!(AuthMode::Basic.to_string(), "basic");
+        assert_eq!(AuthMode::Wsse.to_string(), "wsse");
+        assert_eq!(AuthMode::default(), AuthMode::Basic);
+    }
+
+    #[test]
+    fn round_trip_test() {
+        for auth_mode in [AuthMode::Basic, AuthMode::Wsse] {
+            assert_eq!(AuthMode::from_str(&auth_mode.to_string()), Ok(auth_mode));
+        }
+        let oauth = AuthMode::OAuth(OAuthCredentials::new(
+            "CONSUMER_KEY",
+            "CONSUMER_SECRET",
+            "TOKEN",
+            "TOKEN_SECRET",
+        ));
+        assert_eq!(
+            AuthMode::from_str(&oauth.to_string()),
+            Err(AuthModeParseError { _private: () })
+        );
+    }
+
+    #[test]
+    fn wsse_header_test() {
+        assert_eq!(
+            wsse_header(
+                "HATENA_ID",
+                "API_KEY",
+                b"0123456789abcdef0123",
+                "2013-09-02T11:28:23Z"
+            ),
+            r#"UsernameToken Username="HATENA_ID", PasswordDigest="Kti1Ty34UMfceuF8p0Uj3kcH3Qs=", Nonce="MDEyMzQ1Njc4OWFiY2RlZjAxMjM=", Created="2013-09-02T11:28:23Z""#
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! A blocking `Client`, enabled by the `blocking` feature.
+//!
+//! Each method runs the corresponding `crate::Client` method to completion on a
+//! runtime owned by the client, so it must not be called from within an async runtime.
+
+use std::sync::Arc;
+
+use futures_util::StreamExt as _;
+
+use crate::CategoryDocument;
+use crate::ClientError;
+use crate::Config;
+use crate::CreateEntryResponse;
+use crate::DeleteEntryResponse;
+use crate::Entry;
+use crate::EntryId;
+use crate::EntryParams;
+use crate::FixedDateTime;
+use crate::GetEntryResponse;
+use crate::ListCategoriesResponse;
+use crate::ListEntriesLimits;
+use crate::ListEntriesResponse;
+use crate::PageCursor;
+use crate::UpdateEntryResponse;
+
+#[derive(Clone, Debug)]
+pub struct Client {
+    inner: crate::Client,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl Client {
+    /// # Panics
+    ///
+    /// Panics if the runtime cannot be created.
+    pub fn new(config: &Config) -> Self {
+        Self::from(crate::Client::new(config))
+    }
+
+    pub fn create_entry(
+        &self,
+        entry_params: EntryParams,
+    ) -> Result<CreateEntryResponse, ClientError> {
+        self.runtime.block_on(self.inner.create_entry(entry_params))
+    }
+
+    pub fn delete_entry(&self, entry_id: &EntryId) -> Result<DeleteEntryResponse, ClientError> {
+        self.runtime.block_on(self.inner.delete_entry(entry_id))
+    }
+
+    pub fn get_categories(&self) -> Result<Vec<CategoryDocument>, ClientError> {
+        self.runtime.block_on(self.inner.get_categories())
+    }
+
+    pub fn get_entry(&self, entry_id: &EntryId) -> Result<GetEntryResponse, ClientError> {
+        self.runtime.block_on(self.inner.get_entry(entry_id))
+    }
+
+    pub fn list_categories(&self) -> Result<ListCategoriesResponse, ClientError> {
+        self.runtime.block_on(self.inner.list_categories())
+    }
+
+    /// Returns every entry of the blog. See `crate::Client::list_entries`.
+    pub fn list_entries(
+        &self,
+        limits: ListEntriesLimits,
+    ) -> impl Iterator<Item = Result<Entry, ClientError>> + '_ {
+        let mut entries = Box::pin(self.inner.list_entries(limits));
+        std::iter::from_fn(move || self.runtime.block_on(entries.next()))
+    }
+
+    /// Returns the entries edited since `since`. See `crate::Client::list_entries_edited_since`.
+    pub fn list_entries_edited_since(
+        &self,
+        since: FixedDateTime,
+        assume_edited_within: Option<std::time::Duration>,
+    ) -> impl Iterator<Item = Result<Entry, ClientError>> + '_ {
+        let mut entries = Box::pin(
+            self.inner
+                .list_entries_edited_since(since, assume_edited_within),
+        );
+        std::iter::from_fn(move || self.runtime.block_on(entries.next()))
+    }
+
+    pub fn list_entries_in_page(
+        &self,
+        page: Option<&PageCursor>,
+    ) -> Result<ListEntriesResponse, ClientError> {
+        self.runtime.block_on(self.inner.list_entries_in_page(page))
+    }
+
+    pub fn update_entry(
+        &self,
+        entry_id: &EntryId,
+        entry_params: EntryParams,
+    ) -> Result<UpdateEntryResponse, ClientError> {
+        self.runtime
+            .block_on(self.inner.update_entry(entry_id, entry_params))
+    }
+}
+
+impl From<crate::Client> for Client {
+    /// # Panics
+    ///
+    /// Panics if the runtime cannot be created.
+    fn from(inner: crate::Client) -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to build the runtime for blocking::Client");
+        Self {
+            inner,
+            runtime: Arc::new(runtime),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::ENTRY_XML;
+    use crate::test_fixtures::entry_params;
+    use crate::test_fixtures::mock_config;
+    use crate::test_fixtures::page_xml;
+
+    #[test]
+    fn create_entry_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/test_user/test_blog/atom/entry")
+            .with_status(201)
+            .with_body(ENTRY_XML)
+            .create();
+        let client = Client::new(&mock_config(&server.url()));
+        let response = client.create_entry(entry_params())?;
+        assert_eq!(response.to_string(), ENTRY_XML);
+        mock.assert();
+        Ok(())
+    }
+
+    #[test]
+    fn delete_entry_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("DELETE", "/test_user/test_blog/atom/entry/2500000000")
+            .with_status(200)
+            .create();
+        let client = Client::new(&mock_config(&server.url()));
+        let response = client.delete_entry(&"2500000000".parse::<EntryId>()?)?;
+        assert_eq!(response.to_string(), "");
+        mock.assert();
+        Ok(())
+    }
+
+    #[test]
+    fn get_entry_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", "/test_user/test_blog/atom/entry/2500000000")
+            .with_status(200)
+            .with_body(ENTRY_XML)
+            .create();
+        let client = Client::new(&mock_config(&server.url()));
+        let response = client.get_entry(&"2500000000".parse::<EntryId>()?)?;
+        assert_eq!(response.to_string(), ENTRY_XML);
+        mock.assert();
+        Ok(())
+    }
+
+    #[test]
+    fn get_entry_not_found_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", "/test_user/test_blog/atom/entry/2500000000")
+            .with_status(404)
+            .create();
+        let client = Client::new(&mock_config(&server.url()));
+        let result = client.get_entry(&"2500000000".parse::<EntryId>()?);
+        assert!(matches!(result, Err(ClientError::NotFound(_))));
+        mock.assert();
+        Ok(())
+    }
+
+    #[test]
+    fn list_categories_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", "/test_user/test_blog/atom/category")
+            .with_status(200)
+            .with_body("<app:categories />")
+            .create();
+        let client = Client::new(&mock_config(&server.url()));
+        let response = client.list_categories()?;
+        assert_eq!(response.to_string(), "<app:categories />");
+        mock.assert();
+        Ok(())
+    }
+
+    #[test]
+    fn list_entries_in_page_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", "/test_user/test_blog/atom/entry?page=1377584217")
+            .with_status(200)
+            .with_body("<feed />")
+            .create();
+        let client = Client::new(&mock_config(&server.url()));
+        let response = client.list_entries_in_page(Some(&"1377584217".parse::<PageCursor>()?))?;
+        assert_eq!(response.to_string(), "<feed />");
+        mock.assert();
+        Ok(())
+    }
+
+    #[test]
+    fn list_entries_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new();
+        let first = server
+            .mock("GET", "/test_user/test_blog/atom/entry")
+            .with_status(200)
+            .with_body(page_xml(
+                Some("1377584217"),
+                &[("2500000001", "2013-09-02T11:28:23+09:00")],
+            ))
+            .create();
+        let second = server
+            .mock("GET", "/test_user/test_blog/atom/entry?page=1377584217")
+            .with_status(200)
+            .with_body(page_xml(
+                None,
+                &[("2500000002", "2013-09-02T11:28:23+09:00")],
+            ))
+            .create();
+        let client = Client::new(&mock_config(&server.url()));
+        let entries = client
+            .list_entries(ListEntriesLimits::default())
+            .collect::<Result<Vec<Entry>, ClientError>>()?;
+        assert_eq!(entries.len(), 2);
+        first.assert();
+        second.assert();
+        Ok(())
+    }
+
+    #[test]
+    fn update_entry_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("PUT", "/test_user/test_blog/atom/entry/2500000000")
+            .with_status(200)
+            .with_body(ENTRY_XML)
+            .create();
+        let client = Client::new(&mock_config(&server.url()));
+        let response = client.update_entry(&"2500000000".parse::<EntryId>()?, entry_params())?;
+        assert_eq!(response.to_string(), ENTRY_XML);
+        mock.assert();
+        Ok(())
+    }
+}
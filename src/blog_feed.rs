@@ -0,0 +1,17 @@
+use crate::FixedDateTime;
+
+/// The feed-level metadata of a collection page.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct BlogFeed {
+    pub author_name: Option<String>,
+    /// The URL of the first collection page (`<link rel="first">`).
+    pub first_url: Option<String>,
+    pub id: String,
+    /// The URL of the next collection page (`<link rel="next">`).
+    pub next_url: Option<String>,
+    pub title: String,
+    pub updated: FixedDateTime,
+    /// The public URL of the blog (`<link rel="alternate">`).
+    pub url: Option<String>,
+}
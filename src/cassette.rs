@@ -0,0 +1,443 @@
+//! Record-and-replay `Transport`s, enabled by the `cassette` feature.
+//!
+//! `RecordingTransport` saves the exchanges to a JSON cassette file, and
+//! `ReplayingTransport` serves the saved responses without a network.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use reqwest::Method;
+use reqwest::StatusCode;
+use reqwest::Url;
+use reqwest::header::HeaderMap;
+use reqwest::header::HeaderName;
+use reqwest::header::HeaderValue;
+use serde::Deserialize;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::AuthMode;
+use crate::BoxFuture;
+use crate::Config;
+use crate::HttpRequest;
+use crate::HttpResponse;
+use crate::Transport;
+use crate::TransportError;
+
+const REDACTED: &str = "[REDACTED]";
+
+// headers which carry credentials are never written to a cassette
+const REDACTED_HEADERS: [&str; 4] = ["authorization", "cookie", "set-cookie", "x-wsse"];
+
+#[derive(Debug, Error)]
+pub enum CassetteError {
+    #[error("cassette io error")]
+    Io(#[from] std::io::Error),
+    #[error("cassette format error")]
+    Format(#[from] serde_json::Error),
+    #[error("no recorded interaction matches {method} {url}")]
+    UnmatchedRequest { method: Method, url: String },
+    #[error("invalid recorded interaction")]
+    InvalidInteraction,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+struct Cassette {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct RecordedRequest {
+    method: String,
+    path: String,
+    query: Option<String>,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct RecordedResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+/// A `Transport` which sends requests with another `Transport` and records the
+/// exchanges to a cassette file.
+///
+/// Credential headers are replaced with `[REDACTED]`, and the API key and OAuth
+/// secrets of the `Config` are scrubbed from everything else.
+///
+/// The cassette is written by `flush`, and when the last clone is dropped. Clones
+/// share the recorded exchanges, so keep a clone to call `flush` or `take_errors`
+/// after passing the transport to `ClientBuilder::transport`.
+#[derive(Debug)]
+pub struct RecordingTransport<T> {
+    inner: Arc<T>,
+    recorder: Arc<Recorder>,
+}
+
+#[derive(Debug)]
+struct Recorder {
+    cassette: Mutex<Cassette>,
+    errors: Mutex<Vec<CassetteError>>,
+    path: PathBuf,
+    secrets: Vec<String>,
+}
+
+/// A `Transport` which serves the responses recorded in a cassette file.
+///
+/// Each recorded interaction is served once, to the first request with the same
+/// method, path, query and body. A request which matches no remaining interaction fails.
+#[derive(Clone, Debug)]
+pub struct ReplayingTransport {
+    interactions: Arc<Mutex<Vec<Option<Interaction>>>>,
+}
+
+impl<T: Transport> RecordingTransport<T> {
+    pub fn new(inner: T, path: impl AsRef<Path>, config: &Config) -> Self {
+        let mut secrets = vec![config.api_key.clone()];
+        if let AuthMode::OAuth(credentials) = &config.auth_mode {
+            secrets.push(credentials.consumer_secret.clone());
+            secrets.push(credentials.token.clone());
+            secrets.push(credentials.token_secret.clone());
+        }
+        secrets.retain(|secret| !secret.is_empty());
+        Self {
+            inner: Arc::new(inner),
+            recorder: Arc::new(Recorder {
+                cassette: Mutex::new(Cassette::default()),
+                errors: Mutex::new(vec![]),
+                path: path.as_ref().to_path_buf(),
+                secrets,
+            }),
+        }
+    }
+
+    /// Writes the exchanges recorded so far to the cassette file.
+    pub fn flush(&self) -> Result<(), CassetteError> {
+        self.recorder.flush()
+    }
+
+    /// Returns (and clears) the errors which prevented exchanges from being recorded.
+    ///
+    /// The responses of those exchanges are still returned to `Client`.
+    pub fn take_errors(&self) -> Vec<CassetteError> {
+        std::mem::take(&mut *self.recorder.errors.lock().expect("cassette lock poisoned"))
+    }
+}
+
+impl<T> Clone for RecordingTransport<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            recorder: Arc::clone(&self.recorder),
+        }
+    }
+}
+
+impl<T: Transport> Transport for RecordingTransport<T> {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+        Box::pin(async move {
+            let response = self.inner.send(request.clone()).await?;
+            // the request has been handled by the server, so its response is returned anyway
+            if let Err(e) = self.recorder.record(&request, &response) {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %e, "failed to record interaction");
+                self.recorder
+                    .errors
+                    .lock()
+                    .expect("cassette lock poisoned")
+                    .push(e);
+            }
+            Ok(response)
+        })
+    }
+}
+
+impl Recorder {
+    fn scrub(&self, s: &str) -> String {
+        self.secrets.iter().fold(s.to_string(), |s, secret| {
+            s.replace(secret.as_str(), REDACTED)
+        })
+    }
+
+    fn scrub_headers(&self, headers: &HeaderMap) -> Vec<(String, String)> {
+        headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if REDACTED_HEADERS.contains(&name.as_str()) {
+                    REDACTED.to_string()
+                } else {
+                    self.scrub(&String::from_utf8_lossy(value.as_bytes()))
+                };
+                (name.to_string(), value)
+            })
+            .collect()
+    }
+
+    fn record(&self, request: &HttpRequest, response: &HttpResponse) -> Result<(), CassetteError> {
+        let url = Url::parse(&request.url).map_err(|_| CassetteError::InvalidInteraction)?;
+        let interaction = Interaction {
+            request: RecordedRequest {
+                method: request.method.to_string(),
+                path: self.scrub(url.path()),
+                query: url.query().map(|query| self.scrub(query)),
+                headers: self.scrub_headers(&request.headers),
+                body: request.body.as_deref().map(|body| self.scrub(body)),
+            },
+            response: RecordedResponse {
+                status: response.status.as_u16(),
+                headers: self.scrub_headers(&response.headers),
+                body: self.scrub(&response.body),
+            },
+        };
+        self.cassette
+            .lock()
+            .expect("cassette lock poisoned")
+            .interactions
+            .push(interaction);
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<(), CassetteError> {
+        let json =
+            serde_json::to_string_pretty(&*self.cassette.lock().expect("cassette lock poisoned"))?;
+        std::fs::write(&self.path, json)?;
+        Ok(())
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        // an unused transport does not overwrite the cassette
+        if self
+            .cassette
+            .get_mut()
+            .map_or(true, |cassette| cassette.interactions.is_empty())
+        {
+            return;
+        }
+        // errors cannot be returned from drop; call `flush` to handle them
+        let result = self.flush();
+        #[cfg(feature = "tracing")]
+        if let Err(e) = result {
+            tracing::warn!(error = %e, "failed to write cassette");
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = result;
+    }
+}
+
+impl ReplayingTransport {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, CassetteError> {
+        let cassette = serde_json::from_str::<Cassette>(&std::fs::read_to_string(path)?)?;
+        Ok(Self {
+            interactions: Arc::new(Mutex::new(
+                cassette.interactions.into_iter().map(Some).collect(),
+            )),
+        })
+    }
+
+    fn replay(&self, request: &HttpRequest) -> Result<HttpResponse, CassetteError> {
+        let unmatched = || CassetteError::UnmatchedRequest {
+            method: request.method.clone(),
+            url: request.url.clone(),
+        };
+        let url = Url::parse(&request.url).map_err(|_| unmatched())?;
+        let mut interactions = self.interactions.lock().expect("cassette lock poisoned");
+        let interaction = interactions
+            .iter_mut()
+            .find(|interaction| {
+                interaction.as_ref().is_some_and(|interaction| {
+                    let recorded = &interaction.request;
+                    recorded.method == request.method.as_str()
+                        && recorded.path == url.path()
+                        && recorded.query.as_deref() == url.query()
+                        && recorded.body == request.body
+                })
+            })
+            .and_then(Option::take)
+            .ok_or_else(unmatched)?;
+        let response = interaction.response;
+        let mut headers = HeaderMap::new();
+        for (name, value) in response.headers {
+            headers.append(
+                HeaderName::try_from(name).map_err(|_| CassetteError::InvalidInteraction)?,
+                HeaderValue::try_from(value).map_err(|_| CassetteError::InvalidInteraction)?,
+            );
+        }
+        Ok(HttpResponse {
+            status: StatusCode::from_u16(response.status)
+                .map_err(|_| CassetteError::InvalidInteraction)?,
+            headers,
+            body: response.body,
+        })
+    }
+}
+
+impl Transport for ReplayingTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+        let response = self.replay(&request).map_err(TransportError::new);
+        Box::pin(async move { response })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+    use crate::ClientError;
+    use crate::EntryId;
+    use crate::InMemoryTransport;
+    use crate::PageCursor;
+    use crate::test_fixtures::entry_params;
+
+    fn cassette_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "hatena-blog-api-{}-{}.json",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn config() -> Config {
+        Config::new(
+            "test_user",
+            Some("https://blog.hatena.ne.jp"),
+            "test_blog",
+            "test_api_key",
+        )
+    }
+
+    #[tokio::test]
+    async fn record_and_replay() -> anyhow::Result<()> {
+        let path = cassette_path("record_and_replay");
+        let transport = InMemoryTransport::new(|request| {
+            let mut response = HttpResponse::new(
+                StatusCode::OK,
+                &format!("{} {}", request.method, request.url),
+            );
+            response
+                .headers
+                .insert("x-echo-key", HeaderValue::from_static("test_api_key"));
+            Ok(response)
+        });
+        let recorder = RecordingTransport::new(transport, &path, &config());
+        let recording = Client::builder(&config())
+            .transport(recorder.clone())
+            .build()?;
+        let entry_id = "2500000000".parse::<EntryId>()?;
+        recording.get_entry(&entry_id).await?;
+        recording
+            .list_entries_in_page(Some(&"1377584217".parse::<PageCursor>()?))
+            .await?;
+        assert!(!path.exists());
+        recorder.flush()?;
+        assert!(recorder.take_errors().is_empty());
+
+        let cassette = std::fs::read_to_string(&path)?;
+        assert!(!cassette.contains("test_api_key"));
+        assert!(!cassette.contains("Basic "));
+        let cassette = serde_json::from_str::<Cassette>(&cassette)?;
+        assert_eq!(cassette.interactions.len(), 2);
+        assert_eq!(
+            cassette.interactions[0].request,
+            RecordedRequest {
+                method: "GET".to_string(),
+                path: "/test_user/test_blog/atom/entry/2500000000".to_string(),
+                query: None,
+                headers: vec![("authorization".to_string(), REDACTED.to_string())],
+                body: None,
+            }
+        );
+        assert_eq!(
+            cassette.interactions[1].request.query.as_deref(),
+            Some("page=1377584217")
+        );
+        assert_eq!(
+            cassette.interactions[0].response.headers,
+            vec![("x-echo-key".to_string(), REDACTED.to_string())]
+        );
+
+        // the replayed client may use another base url
+        let replaying = Client::builder(&Config::new(
+            "test_user",
+            Some("http://localhost"),
+            "test_blog",
+            "another_api_key",
+        ))
+        .transport(ReplayingTransport::from_file(&path)?)
+        .build()?;
+        let response = replaying.get_entry(&entry_id).await?;
+        assert_eq!(
+            response.to_string(),
+            "GET https://blog.hatena.ne.jp/test_user/test_blog/atom/entry/2500000000"
+        );
+        // each interaction is served once
+        let result = replaying.get_entry(&entry_id).await;
+        assert!(matches!(result, Err(ClientError::RequestError(_))));
+        let result = replaying
+            .list_entries_in_page(Some(&"1".parse::<PageCursor>()?))
+            .await;
+        assert!(matches!(result, Err(ClientError::RequestError(_))));
+        replaying
+            .list_entries_in_page(Some(&"1377584217".parse::<PageCursor>()?))
+            .await?;
+
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn record_on_drop() -> anyhow::Result<()> {
+        let path = cassette_path("record_on_drop");
+        let transport = InMemoryTransport::new(|_| Ok(HttpResponse::new(StatusCode::OK, "")));
+        let client = Client::builder(&config())
+            .transport(RecordingTransport::new(transport, &path, &config()))
+            .build()?;
+        client
+            .delete_entry(&"2500000000".parse::<EntryId>()?)
+            .await?;
+        assert!(!path.exists());
+        drop(client);
+        let cassette = serde_json::from_str::<Cassette>(&std::fs::read_to_string(&path)?)?;
+        assert_eq!(cassette.interactions.len(), 1);
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn record_error() -> anyhow::Result<()> {
+        let path = cassette_path("record_error");
+        let transport = InMemoryTransport::new(|_| Ok(HttpResponse::new(StatusCode::CREATED, "")));
+        let recorder = RecordingTransport::new(transport, &path, &config());
+        // the url of a request to this base url cannot be recorded
+        let client = Client::builder(&Config::new(
+            "test_user",
+            Some("BASE_URL"),
+            "test_blog",
+            "test_api_key",
+        ))
+        .transport(recorder.clone())
+        .build()?;
+        // the entry has been created, so the response is returned anyway
+        client.create_entry(entry_params()).await?;
+        let errors = recorder.take_errors();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], CassetteError::InvalidInteraction));
+        assert!(recorder.take_errors().is_empty());
+        drop(client);
+        drop(recorder);
+        assert!(!path.exists());
+        Ok(())
+    }
+}
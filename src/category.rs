@@ -0,0 +1,9 @@
+/// An `<atom:category>` of a category document.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Category {
+    pub label: Option<String>,
+    /// The `scheme` of the category, or of its `<app:categories>` if it has none.
+    pub scheme: Option<String>,
+    pub term: String,
+}
@@ -0,0 +1,14 @@
+use crate::Category;
+
+/// An `<app:categories>` with its categories.
+///
+/// A service document may have several `<app:categories>`. Each of them is a separate
+/// `CategoryDocument`, since `fixed` and `scheme` apply only to its own categories.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CategoryDocument {
+    pub categories: Vec<Category>,
+    /// `true` if only the listed categories may be used (`fixed="yes"`).
+    pub fixed: bool,
+    pub scheme: Option<String>,
+}
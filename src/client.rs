@@ -1,48 +1,98 @@
+use crate::AuthMode;
+use crate::CategoryDocument;
+use crate::ClientBuilder;
+use crate::ClientError;
 use crate::Config;
 use crate::CreateEntryResponse;
 use crate::DeleteEntryResponse;
+use crate::Entry;
 use crate::EntryId;
 use crate::EntryParams;
+use crate::FixedDateTime;
 use crate::GetEntryResponse;
+use crate::HttpError;
+use crate::HttpRequest;
+use crate::HttpResponse;
 use crate::ListCategoriesResponse;
+use crate::ListEntriesLimits;
 use crate::ListEntriesResponse;
+use crate::Middleware;
+use crate::PageCursor;
+use crate::RateLimiter;
+use crate::ReqwestTransport;
+use crate::RetryPolicy;
+use crate::Transport;
+use crate::TransportError;
 use crate::UpdateEntryResponse;
+use crate::auth_mode::wsse_header;
+use crate::middleware::Next;
+use crate::response::CategoryBlock;
+use crate::response::inline_category_documents;
+use crate::response::out_of_line_error;
+use crate::response::parse_category_document;
+use crate::retry_policy::parse_retry_after;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use chrono::DateTime;
+use chrono::FixedOffset;
+use chrono::SecondsFormat;
+use chrono::TimeDelta;
+use chrono::Utc;
+use futures_util::Stream;
+use futures_util::TryStreamExt as _;
+use futures_util::stream;
 use reqwest::Method;
 use reqwest::StatusCode;
-use thiserror::Error;
+use reqwest::Url;
+use reqwest::header::AUTHORIZATION;
+use reqwest::header::HeaderMap;
+use reqwest::header::HeaderName;
+use reqwest::header::HeaderValue;
+use reqwest::header::RETRY_AFTER;
+use std::collections::VecDeque;
+use std::sync::Arc;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Client {
     config: Config,
-    http_client: reqwest::Client,
-}
-
-#[derive(Debug, Error)]
-pub enum ClientError {
-    #[error("request error")]
-    RequestError(#[from] reqwest::Error),
-    #[error("bad request")]
-    BadRequest,
-    #[error("unauthorized")]
-    Unauthorized,
-    #[error("not found")]
-    NotFound,
-    #[error("method not allowed")]
-    MethodNotAllowed,
-    #[error("internal server error")]
-    InternalServerError,
-    #[error("unknown status code")]
-    UnknownStatusCode,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    rate_limiter: Option<RateLimiter>,
+    retry_policy: Option<RetryPolicy>,
+    transport: Arc<dyn Transport>,
 }
 
 impl Client {
     pub fn new(config: &Config) -> Self {
+        Self::from_parts(
+            config.clone(),
+            Arc::new(ReqwestTransport::default()),
+            vec![],
+            None,
+            None,
+        )
+    }
+
+    pub fn builder(config: &Config) -> ClientBuilder {
+        ClientBuilder::new(config)
+    }
+
+    pub(crate) fn from_parts(
+        config: Config,
+        transport: Arc<dyn Transport>,
+        middlewares: Vec<Arc<dyn Middleware>>,
+        rate_limiter: Option<RateLimiter>,
+        retry_policy: Option<RetryPolicy>,
+    ) -> Self {
         Self {
-            config: config.clone(),
-            http_client: reqwest::Client::new(),
+            config,
+            middlewares,
+            rate_limiter,
+            retry_policy,
+            transport,
         }
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn create_entry(
         &self,
         entry_params: EntryParams,
@@ -53,6 +103,7 @@ impl Client {
             .map(CreateEntryResponse::from)
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entry_id = %entry_id)))]
     pub async fn delete_entry(
         &self,
         entry_id: &EntryId,
@@ -62,27 +113,168 @@ impl Client {
             .map(DeleteEntryResponse::from)
     }
 
+    /// Returns the category documents of the blog, one for each `<app:categories>`.
+    ///
+    /// Out-of-line category documents (`<app:categories href="..." />`) are fetched
+    /// in their place, but the documents they refer to must list their categories inline.
+    /// Since the request is authenticated, only an href with the same scheme, host and
+    /// port as `Config::base_url` is fetched; any other href is an `OutOfLine` error.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn get_categories(&self) -> Result<Vec<CategoryDocument>, ClientError> {
+        let url = self.category_document_uri();
+        let body = self.request(Method::GET, &url, None).await?;
+        let base_url = Url::parse(&self.config.base_url).ok();
+        let mut documents = vec![];
+        for block in parse_category_document(&body)? {
+            let (href, position) = match block {
+                CategoryBlock::Inline(document) => {
+                    documents.push(document);
+                    continue;
+                }
+                CategoryBlock::OutOfLine { href, position } => (href, position),
+            };
+            // the href may be relative to the category document
+            let resolved = Url::parse(&url).and_then(|url| url.join(&href)).ok();
+            // the credentials must not be sent to another origin
+            let resolved = match (resolved, &base_url) {
+                (Some(resolved), Some(base_url)) if resolved.origin() == base_url.origin() => {
+                    resolved
+                }
+                _ => return Err(ClientError::from(out_of_line_error(href, position))),
+            };
+            let body = self.request(Method::GET, resolved.as_str(), None).await?;
+            documents.extend(inline_category_documents(parse_category_document(&body)?)?);
+        }
+        Ok(documents)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entry_id = %entry_id)))]
     pub async fn get_entry(&self, entry_id: &EntryId) -> Result<GetEntryResponse, ClientError> {
         self.request(Method::GET, &self.member_uri(entry_id), None)
             .await
             .map(GetEntryResponse::from)
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn list_categories(&self) -> Result<ListCategoriesResponse, ClientError> {
         self.request(Method::GET, &self.category_document_uri(), None)
             .await
             .map(ListCategoriesResponse::from)
     }
 
+    /// Returns every entry of the blog, following the `next` links of the collection
+    /// pages until the last page or until one of the `limits` is reached.
+    ///
+    /// Pages are fetched lazily as the stream is polled. The stream ends after the
+    /// first error.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn list_entries(
+        &self,
+        limits: ListEntriesLimits,
+    ) -> impl Stream<Item = Result<Entry, ClientError>> + Send + '_ {
+        struct State {
+            entries: VecDeque<Entry>,
+            has_next_page: bool,
+            next_page: Option<PageCursor>,
+            pages: usize,
+            yielded: usize,
+        }
+        let state = State {
+            entries: VecDeque::new(),
+            has_next_page: true,
+            next_page: None,
+            pages: 0,
+            yielded: 0,
+        };
+        // the pages are fetched in the span of this call, not in the span of the poller
+        #[cfg(feature = "tracing")]
+        let span = tracing::Span::current();
+        stream::unfold(state, move |mut state| {
+            let next = async move {
+                loop {
+                    if limits.entries_reached(state.yielded) {
+                        return None;
+                    }
+                    if let Some(entry) = state.entries.pop_front() {
+                        state.yielded += 1;
+                        return Some((Ok(entry), state));
+                    }
+                    if !state.has_next_page || limits.pages_reached(state.pages) {
+                        return None;
+                    }
+                    state.pages += 1;
+                    // the stream ends after an error
+                    state.has_next_page = false;
+                    let result = self
+                        .list_entries_in_page(state.next_page.as_ref())
+                        .await
+                        .and_then(|response| {
+                            <(Option<PageCursor>, Vec<Entry>)>::try_from(response)
+                                .map_err(ClientError::from)
+                        });
+                    match result {
+                        Ok((next_page, entries)) => {
+                            state.has_next_page = next_page.is_some()
+                                && !entries.is_empty()
+                                && !limits.published_passed(&entries);
+                            state.next_page = next_page;
+                            state.entries = VecDeque::from(entries);
+                        }
+                        Err(e) => return Some((Err(e), state)),
+                    }
+                }
+            };
+            #[cfg(feature = "tracing")]
+            let next = tracing::Instrument::instrument(next, span.clone());
+            next
+        })
+    }
+
+    /// Returns the entries whose `app:edited` is later than `since`.
+    ///
+    /// Hatena sorts the collection by the published date, not by the edited date, and an
+    /// entry published long ago may have been edited after `since`. No page tells that
+    /// the later pages have no such entry, so a walk that stops early cannot be correct.
+    /// With `assume_edited_within: None` every page is fetched and the entries are
+    /// filtered, which is correct but makes as many requests as `list_entries`.
+    ///
+    /// `assume_edited_within` trades completeness for fewer requests: it assumes that
+    /// every entry is edited within that duration after it is published, and no page is
+    /// fetched after a page whose entries were all published before `since` minus it.
+    /// An entry edited later than that after it was published is missed.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(since = %since)))]
+    pub fn list_entries_edited_since(
+        &self,
+        since: FixedDateTime,
+        assume_edited_within: Option<std::time::Duration>,
+    ) -> impl Stream<Item = Result<Entry, ClientError>> + Send + '_ {
+        let stop_before_published = assume_edited_within.and_then(|within| {
+            let since = DateTime::<FixedOffset>::from(since);
+            // an overflowing duration bounds nothing
+            TimeDelta::from_std(within)
+                .ok()
+                .and_then(|within| since.checked_sub_signed(within))
+                .map(FixedDateTime::from)
+        });
+        let limits = ListEntriesLimits {
+            stop_before_published,
+            ..ListEntriesLimits::default()
+        };
+        self.list_entries(limits)
+            .try_filter(move |entry| std::future::ready(entry.edited > since))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(page = ?page)))]
     pub async fn list_entries_in_page(
         &self,
-        page: Option<&str>,
+        page: Option<&PageCursor>,
     ) -> Result<ListEntriesResponse, ClientError> {
         self.request(Method::GET, &self.collection_uri(page), None)
             .await
             .map(ListEntriesResponse::from)
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entry_id = %entry_id)))]
     pub async fn update_entry(
         &self,
         entry_id: &EntryId,
@@ -102,15 +294,15 @@ impl Client {
         )
     }
 
-    fn collection_uri(&self, page: Option<&str>) -> String {
+    fn collection_uri(&self, page: Option<&PageCursor>) -> String {
         let config = &self.config;
         format!(
             "{}/{}/{}/atom/entry{}",
             config.base_url,
             config.hatena_id,
             config.blog_id,
-            page.map(|s| format!("?page={}", urlencoding::encode(s)))
-                .unwrap_or_else(|| "".to_string())
+            page.map(|page| format!("?page={}", urlencoding::encode(&page.to_string())))
+                .unwrap_or_default()
         )
     }
 
@@ -122,41 +314,158 @@ impl Client {
         )
     }
 
+    fn authorization_header(
+        &self,
+        method: &Method,
+        url: &str,
+    ) -> Result<(HeaderName, HeaderValue), TransportError> {
+        let config = &self.config;
+        let (name, value) = match &config.auth_mode {
+            AuthMode::Basic => (
+                AUTHORIZATION,
+                format!(
+                    "Basic {}",
+                    STANDARD.encode(format!("{}:{}", config.hatena_id, config.api_key))
+                ),
+            ),
+            AuthMode::Wsse => {
+                let nonce = rand::random::<[u8; 20]>();
+                let created = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+                (
+                    HeaderName::from_static("x-wsse"),
+                    wsse_header(&config.hatena_id, &config.api_key, &nonce, &created),
+                )
+            }
+            AuthMode::OAuth(credentials) => {
+                let url = Url::parse(url).map_err(TransportError::new)?;
+                let nonce = format!("{:032x}", rand::random::<u128>());
+                let timestamp = Utc::now().timestamp();
+                (
+                    AUTHORIZATION,
+                    credentials.authorization_header(method.as_str(), &url, &nonce, timestamp),
+                )
+            }
+        };
+        let mut value = HeaderValue::from_str(&value).map_err(TransportError::new)?;
+        value.set_sensitive(true);
+        Ok((name, value))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "request",
+            skip_all,
+            fields(
+                method = %method,
+                url = %url,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    async fn send(
+        &self,
+        method: &Method,
+        url: &str,
+        body: Option<&str>,
+    ) -> Result<String, ClientError> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+        let (name, value) = self.authorization_header(method, url)?;
+        let mut headers = HeaderMap::new();
+        headers.insert(name, value);
+        let request = HttpRequest {
+            method: method.clone(),
+            url: url.to_string(),
+            headers,
+            body: body.map(|body| body.to_string()),
+        };
+        #[cfg(feature = "tracing")]
+        let start = std::time::Instant::now();
+        let response = Next::new(&self.middlewares, self.transport.as_ref())
+            .run(request)
+            .await;
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record("latency_ms", start.elapsed().as_millis());
+            match &response {
+                Ok(response) => {
+                    span.record("status", response.status.as_u16());
+                    tracing::debug!("response received");
+                }
+                Err(e) => tracing::warn!(error = %e, "request failed"),
+            }
+        }
+        let response = response?;
+        if response.status.is_success() {
+            return Ok(response.body);
+        }
+        let retry_after = retry_after(&response);
+        Err(ClientError::from(HttpError::new(
+            response.status,
+            method.clone(),
+            url.to_string(),
+            response.body,
+            retry_after,
+        )))
+    }
+
     async fn request(
         &self,
         method: Method,
         url: &str,
         body: Option<String>,
     ) -> Result<String, ClientError> {
-        let config = &self.config;
-        let request = self
-            .http_client
-            .request(method, url)
-            .basic_auth(&config.hatena_id, Some(&config.api_key));
-        let request = if let Some(body) = body {
-            request.body(body)
-        } else {
-            request
-        };
-        let response = request.send().await?;
-        match response.status() {
-            status_code if status_code.is_success() => {
-                let body = response.text().await?;
-                Ok(body)
+        let mut attempt = 1;
+        loop {
+            let result = self.send(&method, url, body.as_deref()).await;
+            let retry_policy = self
+                .retry_policy
+                .as_ref()
+                .filter(|policy| attempt < policy.max_attempts && policy.retries_method(&method));
+            match (result, retry_policy) {
+                (Err(e), Some(policy)) if e.is_retryable() => {
+                    let retry_after = e.http_error().and_then(HttpError::retry_after);
+                    match policy.delay(attempt, retry_after) {
+                        Some(delay) => tokio::time::sleep(delay).await,
+                        None => return Err(e),
+                    }
+                    attempt += 1;
+                }
+                (result, _) => return result,
             }
-            StatusCode::BAD_REQUEST => Err(ClientError::BadRequest),
-            StatusCode::UNAUTHORIZED => Err(ClientError::Unauthorized),
-            StatusCode::NOT_FOUND => Err(ClientError::NotFound),
-            StatusCode::METHOD_NOT_ALLOWED => Err(ClientError::MethodNotAllowed),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(ClientError::InternalServerError),
-            _ => Err(ClientError::UnknownStatusCode),
         }
     }
 }
 
+// Retry-After is honored on 429 Too Many Requests and 503 Service Unavailable
+fn retry_after(response: &HttpResponse) -> Option<std::time::Duration> {
+    match response.status {
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE => response
+            .headers
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, Utc::now())),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::EntryStatus;
+    use crate::InMemoryTransport;
+    use crate::OAuthCredentials;
+    use crate::ParseCategoryErrorKind;
+    use crate::test_fixtures::ENTRY_XML;
+    use crate::test_fixtures::entry_params;
+    use crate::test_fixtures::mock_config;
+    use crate::test_fixtures::page_xml;
+    use crate::test_fixtures::page_xml_with_published;
+    use futures_util::StreamExt as _;
 
     fn config() -> Config {
         Config::new("HATENA_ID", Some("BASE_URL"), "BLOG_ID", "API_KEY")
@@ -169,6 +478,559 @@ mod test {
         assert_eq!(client.config, config);
     }
 
+    #[tokio::test]
+    async fn get_entry_with_in_memory_transport() -> anyhow::Result<()> {
+        let transport =
+            InMemoryTransport::new(|_| Ok(HttpResponse::new(StatusCode::OK, ENTRY_XML)));
+        let client = Client::builder(&mock_config("https://blog.hatena.ne.jp"))
+            .transport(transport.clone())
+            .build()?;
+        let entry_id = "2500000000".parse::<EntryId>()?;
+        let response = client.get_entry(&entry_id).await?;
+        assert_eq!(response.to_string(), ENTRY_XML);
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, Method::GET);
+        assert_eq!(
+            requests[0].url,
+            "https://blog.hatena.ne.jp/test_user/test_blog/atom/entry/2500000000"
+        );
+        assert_eq!(
+            requests[0].headers.get(AUTHORIZATION),
+            Some(&HeaderValue::from_static(
+                "Basic dGVzdF91c2VyOnRlc3RfYXBpX2tleQ=="
+            ))
+        );
+        assert_eq!(requests[0].body, None);
+        Ok(())
+    }
+
+    #[derive(Debug, Default)]
+    struct FailFirst {
+        failed: std::sync::atomic::AtomicBool,
+    }
+
+    impl Middleware for FailFirst {
+        fn handle<'a>(
+            &'a self,
+            request: HttpRequest,
+            next: Next<'a>,
+        ) -> crate::BoxFuture<'a, Result<HttpResponse, TransportError>> {
+            Box::pin(async move {
+                if self.failed.swap(true, std::sync::atomic::Ordering::SeqCst) {
+                    next.run(request).await
+                } else {
+                    Ok(HttpResponse::new(StatusCode::SERVICE_UNAVAILABLE, ""))
+                }
+            })
+        }
+    }
+
+    #[derive(Debug)]
+    struct NotFoundToEmpty;
+
+    impl Middleware for NotFoundToEmpty {
+        fn handle<'a>(
+            &'a self,
+            request: HttpRequest,
+            next: Next<'a>,
+        ) -> crate::BoxFuture<'a, Result<HttpResponse, TransportError>> {
+            Box::pin(async move {
+                let response = next.run(request).await?;
+                Ok(match response.status {
+                    StatusCode::NOT_FOUND => HttpResponse::new(StatusCode::OK, ""),
+                    _ => response,
+                })
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn update_entry_keeps_content_type_with_in_memory_transport() -> anyhow::Result<()> {
+        let transport = InMemoryTransport::new(|request| {
+            let body = match request.method {
+                Method::GET => ENTRY_XML
+                    .replace("text/x-hatena-syntax", "text/x-markdown")
+                    .replace(
+                        "<category term=\"Scala\" />",
+                        "<hatenablog:custom-url xmlns:hatenablog=\"http://www.hatena.ne.jp/info/xmlns#hatenablog\">2013/09/02/a</hatenablog:custom-url>",
+                    ),
+                _ => ENTRY_XML.to_string(),
+            };
+            Ok(HttpResponse::new(StatusCode::OK, &body))
+        });
+        let client = Client::builder(&mock_config("https://blog.hatena.ne.jp"))
+            .transport(transport.clone())
+            .build()?;
+        let entry_id = "2500000000".parse::<EntryId>()?;
+        let entry = Entry::try_from(client.get_entry(&entry_id).await?)?;
+        assert_eq!(entry.content_type, Some(crate::ContentType::Markdown));
+        client
+            .update_entry(&entry_id, EntryParams::try_from(&entry)?)
+            .await?;
+        let requests = transport.requests();
+        let body = requests[1].body.as_deref().unwrap_or_default();
+        assert!(
+            body.contains(r#"<content type="text/x-markdown">"#),
+            "{body}"
+        );
+        assert!(
+            body.contains("<hatenablog:custom-url>2013/09/02/a</hatenablog:custom-url>"),
+            "{body}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_entry_with_middlewares() -> anyhow::Result<()> {
+        let transport =
+            InMemoryTransport::new(|_| Ok(HttpResponse::new(StatusCode::NOT_FOUND, "")));
+        let client = Client::builder(&mock_config("https://blog.hatena.ne.jp"))
+            .transport(transport.clone())
+            .middleware(FailFirst::default())
+            .middleware(NotFoundToEmpty)
+            .retry_policy(retry_policy())
+            .build()?;
+        let entry_id = "2500000000".parse::<EntryId>()?;
+        let response = client.get_entry(&entry_id).await?;
+        assert_eq!(response.to_string(), "");
+        // the first attempt is short-circuited by FailFirst and retried
+        assert_eq!(transport.requests().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_entry_transport_error_with_in_memory_transport() -> anyhow::Result<()> {
+        let transport = InMemoryTransport::new(|request| match request.method {
+            Method::GET => Err(TransportError::retryable("connection reset")),
+            _ => Err(TransportError::new("unexpected method")),
+        });
+        let client = Client::builder(&mock_config("https://blog.hatena.ne.jp"))
+            .transport(transport.clone())
+            .retry_policy(retry_policy())
+            .build()?;
+        let entry_id = "2500000000".parse::<EntryId>()?;
+        let result = client.get_entry(&entry_id).await;
+        assert!(matches!(result, Err(ClientError::RequestError(_))));
+        assert_eq!(transport.requests().len(), 3);
+
+        let result = client.delete_entry(&entry_id).await;
+        assert!(matches!(result, Err(ClientError::RequestError(_))));
+        assert_eq!(transport.requests().len(), 4);
+        Ok(())
+    }
+
+    fn paged_transport() -> InMemoryTransport {
+        InMemoryTransport::new(|request| {
+            let body = match Url::parse(&request.url)
+                .map_err(TransportError::new)?
+                .query()
+            {
+                None => page_xml(
+                    Some("2"),
+                    &[
+                        ("2500000001", "2013-09-05T00:00:00+09:00"),
+                        ("2500000002", "2013-09-02T00:00:00+09:00"),
+                    ],
+                ),
+                Some("page=2") => {
+                    page_xml(Some("3"), &[("2500000003", "2013-09-01T00:00:00+09:00")])
+                }
+                // published long ago, but edited recently
+                Some("page=3") => page_xml(None, &[("2500000004", "2013-09-10T00:00:00+09:00")]),
+                Some(_) => return Ok(HttpResponse::new(StatusCode::NOT_FOUND, "")),
+            };
+            Ok(HttpResponse::new(StatusCode::OK, &body))
+        })
+    }
+
+    async fn list_entry_ids(
+        client: &Client,
+        limits: ListEntriesLimits,
+    ) -> Result<Vec<String>, ClientError> {
+        client
+            .list_entries(limits)
+            .map_ok(|entry| entry.id.to_string())
+            .try_collect::<Vec<String>>()
+            .await
+    }
+
+    #[tokio::test]
+    async fn list_entries_with_in_memory_transport() -> anyhow::Result<()> {
+        let transport = paged_transport();
+        let client = Client::builder(&mock_config("https://blog.hatena.ne.jp"))
+            .transport(transport.clone())
+            .build()?;
+        let entry_ids = list_entry_ids(&client, ListEntriesLimits::default()).await?;
+        assert_eq!(
+            entry_ids,
+            vec!["2500000001", "2500000002", "2500000003", "2500000004"]
+        );
+        let urls = transport
+            .requests()
+            .into_iter()
+            .map(|request| request.url)
+            .collect::<Vec<String>>();
+        assert_eq!(
+            urls,
+            vec![
+                "https://blog.hatena.ne.jp/test_user/test_blog/atom/entry",
+                "https://blog.hatena.ne.jp/test_user/test_blog/atom/entry?page=2",
+                "https://blog.hatena.ne.jp/test_user/test_blog/atom/entry?page=3",
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn list_entries_with_limits_with_in_memory_transport() -> anyhow::Result<()> {
+        let transport = paged_transport();
+        let client = Client::builder(&mock_config("https://blog.hatena.ne.jp"))
+            .transport(transport.clone())
+            .build()?;
+
+        let limits = ListEntriesLimits::default().with_max_pages(2);
+        let entry_ids = list_entry_ids(&client, limits).await?;
+        assert_eq!(entry_ids, vec!["2500000001", "2500000002", "2500000003"]);
+        assert_eq!(transport.requests().len(), 2);
+
+        // no page is fetched once max_entries is reached
+        let limits = ListEntriesLimits::default().with_max_entries(2);
+        let entry_ids = list_entry_ids(&client, limits).await?;
+        assert_eq!(entry_ids, vec!["2500000001", "2500000002"]);
+        assert_eq!(transport.requests().len(), 3);
+
+        let limits = ListEntriesLimits::default()
+            .with_max_entries(3)
+            .with_max_pages(1);
+        let entry_ids = list_entry_ids(&client, limits).await?;
+        assert_eq!(entry_ids, vec!["2500000001", "2500000002"]);
+        assert_eq!(transport.requests().len(), 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn list_entries_edited_since_with_in_memory_transport() -> anyhow::Result<()> {
+        let transport = paged_transport();
+        let client = Client::builder(&mock_config("https://blog.hatena.ne.jp"))
+            .transport(transport.clone())
+            .build()?;
+        let since = "2013-09-02T00:00:00+09:00".parse::<FixedDateTime>()?;
+        let entry_ids = client
+            .list_entries_edited_since(since, None)
+            .map_ok(|entry| entry.id.to_string())
+            .try_collect::<Vec<String>>()
+            .await?;
+        assert_eq!(entry_ids, vec!["2500000001", "2500000004"]);
+        assert_eq!(transport.requests().len(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn list_entries_edited_since_returns_old_entries_edited_recently() -> anyhow::Result<()> {
+        let transport = InMemoryTransport::new(|request| {
+            let body = match Url::parse(&request.url)
+                .map_err(TransportError::new)?
+                .query()
+            {
+                None => page_xml_with_published(
+                    Some("2"),
+                    &[(
+                        "2500000001",
+                        "2013-08-01T00:00:00+09:00",
+                        "2013-08-01T00:00:00+09:00",
+                    )],
+                ),
+                // published long before `since`, but edited after it
+                _ => page_xml_with_published(
+                    None,
+                    &[(
+                        "2500000002",
+                        "2012-01-01T00:00:00+09:00",
+                        "2013-09-10T00:00:00+09:00",
+                    )],
+                ),
+            };
+            Ok(HttpResponse::new(StatusCode::OK, &body))
+        });
+        let client = Client::builder(&mock_config("https://blog.hatena.ne.jp"))
+            .transport(transport.clone())
+            .build()?;
+        let since = "2013-09-03T00:00:00+09:00".parse::<FixedDateTime>()?;
+        let entry_ids = client
+            .list_entries_edited_since(since, None)
+            .map_ok(|entry| entry.id.to_string())
+            .try_collect::<Vec<String>>()
+            .await?;
+        assert_eq!(entry_ids, vec!["2500000002"]);
+        assert_eq!(transport.requests().len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn list_entries_edited_since_with_assume_edited_within() -> anyhow::Result<()> {
+        let transport = InMemoryTransport::new(|request| {
+            let body = match Url::parse(&request.url)
+                .map_err(TransportError::new)?
+                .query()
+            {
+                None => page_xml_with_published(
+                    Some("2"),
+                    &[(
+                        "2500000001",
+                        "2013-09-05T00:00:00+09:00",
+                        "2013-09-05T00:00:00+09:00",
+                    )],
+                ),
+                // published before the cutoff, but edited after `since`
+                Some("page=2") => page_xml_with_published(
+                    Some("3"),
+                    &[(
+                        "2500000002",
+                        "2013-09-01T00:00:00+09:00",
+                        "2013-09-10T00:00:00+09:00",
+                    )],
+                ),
+                Some(_) => page_xml_with_published(
+                    None,
+                    &[(
+                        "2500000003",
+                        "2013-08-01T00:00:00+09:00",
+                        "2013-08-01T00:00:00+09:00",
+                    )],
+                ),
+            };
+            Ok(HttpResponse::new(StatusCode::OK, &body))
+        });
+        let client = Client::builder(&mock_config("https://blog.hatena.ne.jp"))
+            .transport(transport.clone())
+            .build()?;
+        let since = "2013-09-03T00:00:00+09:00".parse::<FixedDateTime>()?;
+        let entry_ids = client
+            .list_entries_edited_since(since, Some(std::time::Duration::from_secs(86400)))
+            .map_ok(|entry| entry.id.to_string())
+            .try_collect::<Vec<String>>()
+            .await?;
+        assert_eq!(entry_ids, vec!["2500000001", "2500000002"]);
+        // the page after the first page published before the cutoff is not requested
+        let urls = transport
+            .requests()
+            .into_iter()
+            .map(|request| request.url)
+            .collect::<Vec<String>>();
+        assert_eq!(
+            urls,
+            vec![
+                "https://blog.hatena.ne.jp/test_user/test_blog/atom/entry",
+                "https://blog.hatena.ne.jp/test_user/test_blog/atom/entry?page=2",
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn list_entries_error_with_in_memory_transport() -> anyhow::Result<()> {
+        let transport = InMemoryTransport::new(|request| {
+            Ok(if request.url.ends_with("?page=2") {
+                HttpResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "")
+            } else {
+                HttpResponse::new(
+                    StatusCode::OK,
+                    &page_xml(Some("2"), &[("2500000001", "2013-09-02T11:28:23+09:00")]),
+                )
+            })
+        });
+        let client = Client::builder(&mock_config("https://blog.hatena.ne.jp"))
+            .transport(transport)
+            .build()?;
+        let results = client
+            .list_entries(ListEntriesLimits::default())
+            .collect::<Vec<Result<Entry, ClientError>>>()
+            .await;
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(ClientError::InternalServerError(_))
+        ));
+
+        let transport = InMemoryTransport::new(|_| {
+            Ok(HttpResponse::new(StatusCode::OK, "<feed><entry /></feed>"))
+        });
+        let client = Client::builder(&mock_config("https://blog.hatena.ne.jp"))
+            .transport(transport)
+            .build()?;
+        let result = list_entry_ids(&client, ListEntriesLimits::default()).await;
+        assert!(matches!(result, Err(ClientError::ParseEntryError(_))));
+        Ok(())
+    }
+
+    #[cfg(feature = "tracing")]
+    #[derive(Clone, Default)]
+    struct CapturingLayer {
+        spans: Arc<std::sync::Mutex<Vec<String>>>,
+        // (span name or "event", field name, value)
+        fields: Arc<std::sync::Mutex<Vec<(String, String, String)>>>,
+    }
+
+    #[cfg(feature = "tracing")]
+    impl CapturingLayer {
+        fn record(&self, name: &str, values: &dyn Fn(&mut dyn tracing::field::Visit)) {
+            struct Visitor<'a>(&'a str, &'a mut Vec<(String, String, String)>);
+            impl tracing::field::Visit for Visitor<'_> {
+                fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+                    self.1.push((
+                        self.0.to_string(),
+                        field.name().to_string(),
+                        value.to_string(),
+                    ));
+                }
+                fn record_debug(
+                    &mut self,
+                    field: &tracing::field::Field,
+                    value: &dyn std::fmt::Debug,
+                ) {
+                    self.1.push((
+                        self.0.to_string(),
+                        field.name().to_string(),
+                        format!("{value:?}"),
+                    ));
+                }
+            }
+            let mut fields = self.fields.lock().unwrap();
+            values(&mut Visitor(name, &mut fields));
+        }
+
+        fn has_field(&self, name: &str, field: &str, value: &str) -> bool {
+            self.fields
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|(n, f, v)| n == name && f == field && v == value)
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    impl<S> tracing_subscriber::Layer<S> for CapturingLayer
+    where
+        S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            _: &tracing::span::Id,
+            _: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let name = attrs.metadata().name();
+            self.spans.lock().unwrap().push(name.to_string());
+            self.record(name, &|visitor| attrs.record(visitor));
+        }
+
+        fn on_record(
+            &self,
+            id: &tracing::span::Id,
+            values: &tracing::span::Record<'_>,
+            ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let name = ctx.span(id).map(|span| span.name()).unwrap_or_default();
+            self.record(name, &|visitor| values.record(visitor));
+        }
+
+        fn on_event(
+            &self,
+            event: &tracing::Event<'_>,
+            _: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            self.record("event", &|visitor| event.record(visitor));
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn tracing_with_in_memory_transport() -> anyhow::Result<()> {
+        use tracing_subscriber::layer::SubscriberExt as _;
+
+        let layer = CapturingLayer::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(layer.clone()));
+        let transport = InMemoryTransport::new(|request| {
+            let body = if request.url.ends_with("/atom/entry") {
+                page_xml(None, &[("2500000001", "2013-09-02T11:28:23+09:00")])
+            } else if request.url.ends_with("/atom/category") {
+                "<service />".to_string()
+            } else {
+                "<entry />".to_string()
+            };
+            Ok(HttpResponse::new(StatusCode::OK, &body))
+        });
+        let entry_id = "2500000000".parse::<EntryId>()?;
+        for auth_mode in [AuthMode::Basic, AuthMode::Wsse] {
+            let config = mock_config("https://blog.hatena.ne.jp").with_auth_mode(auth_mode);
+            let client = Client::builder(&config)
+                .transport(transport.clone())
+                .build()?;
+            list_entry_ids(&client, ListEntriesLimits::default()).await?;
+            client
+                .list_entries_edited_since("2013-09-01T00:00:00+09:00".parse()?, None)
+                .try_collect::<Vec<Entry>>()
+                .await?;
+            let response = client.get_entry(&entry_id).await?;
+            assert!(Entry::try_from(response).is_err());
+            assert!(client.get_categories().await.is_err());
+        }
+
+        let spans = layer.spans.lock().unwrap().clone();
+        for name in [
+            "get_categories",
+            "get_entry",
+            "list_entries",
+            "list_entries_edited_since",
+            "list_entries_in_page",
+            "request",
+        ] {
+            assert!(spans.iter().any(|span| span == name), "{name}");
+        }
+        assert!(layer.has_field("request", "method", "GET"));
+        assert!(layer.has_field(
+            "request",
+            "url",
+            "https://blog.hatena.ne.jp/test_user/test_blog/atom/entry/2500000000"
+        ));
+        assert!(layer.has_field("request", "status", "200"));
+        assert!(
+            layer
+                .fields
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|(name, field, _)| name == "request" && field == "latency_ms")
+        );
+        assert!(layer.has_field("event", "message", "failed to parse entry"));
+        assert!(layer.has_field("event", "message", "failed to parse category document"));
+
+        // the credentials were sent, but they are neither in a span nor in an event
+        let secrets = transport
+            .requests()
+            .iter()
+            .flat_map(|request| request.headers.values())
+            .map(|value| value.to_str().map(str::to_string))
+            .collect::<Result<Vec<String>, _>>()?;
+        assert!(secrets.iter().any(|secret| secret.starts_with("Basic ")));
+        assert!(
+            secrets
+                .iter()
+                .any(|secret| secret.starts_with("UsernameToken "))
+        );
+        for (_, _, value) in layer.fields.lock().unwrap().iter() {
+            assert!(!value.contains("test_api_key"), "{value}");
+            assert!(
+                !secrets.iter().any(|secret| value.contains(secret.as_str())),
+                "{value}"
+            );
+        }
+        Ok(())
+    }
+
     #[test]
     fn collection_uri() {
         let client = Client::new(&config());
@@ -219,40 +1081,6 @@ mod test {
         // See: examples/update_entry.rs
     }
 
-    const ENTRY_XML: &str = r#"<?xml version="1.0" encoding="utf-8"?>
-<entry xmlns="http://www.w3.org/2005/Atom"
-       xmlns:app="http://www.w3.org/2007/app">
-  <id>tag:blog.hatena.ne.jp,2013:blog-test_user-20000000000000-3000000000000000</id>
-  <link rel="edit" href="https://blog.hatena.ne.jp/test_user/test_blog/atom/entry/2500000000"/>
-  <link rel="alternate" type="text/html" href="http://test_blog.hatenablog.com/entry/2013/09/02/112823"/>
-  <author><name>test_user</name></author>
-  <title>記事タイトル</title>
-  <updated>2013-09-02T11:28:23+09:00</updated>
-  <published>2013-09-02T11:28:23+09:00</published>
-  <app:edited>2013-09-02T11:28:23+09:00</app:edited>
-  <summary type="text"> 記事本文 リスト1 リスト2 内容 </summary>
-  <content type="text/x-hatena-syntax">
-** 記事本文
-- リスト1
-- リスト2
-内容
-  </content>
-  <hatena:formatted-content type="text/html" xmlns:hatena="http://www.hatena.ne.jp/info/xmlns#">
-    <div class="section">
-    <h4>記事本文</h4>
-    <ul>
-    <li>リスト1</li>
-    <li>リスト2</li>
-    </ul><p>内容</p>
-    </div>
-  </hatena:formatted-content>
-  <category term="Scala" />
-  <category term="Perl" />
-  <app:control>
-    <app:draft>no</app:draft>
-  </app:control>
-</entry>"#;
-
     const FEED_XML: &str = r#"<?xml version="1.0" encoding="utf-8"?>
 <feed xmlns="http://www.w3.org/2005/Atom"
       xmlns:app="http://www.w3.org/2007/app">
@@ -308,10 +1136,6 @@ mod test {
   <atom:category term="Scala" />
 </app:categories>"#;
 
-    fn mock_config(server_url: &str) -> Config {
-        Config::new("test_user", Some(server_url), "test_blog", "test_api_key")
-    }
-
     #[tokio::test]
     async fn create_entry_with_mock() -> anyhow::Result<()> {
         let mut server = mockito::Server::new_async().await;
@@ -326,14 +1150,7 @@ mod test {
             .create_async()
             .await;
         let client = Client::new(&mock_config(&server.url()));
-        let params = EntryParams::new(
-            "test_user".to_string(),
-            "記事タイトル".to_string(),
-            "** 記事本文".to_string(),
-            "2013-09-02T11:28:23+09:00".to_string(),
-            vec!["Scala".to_string()],
-            false,
-        );
+        let params = entry_params();
         let response = client.create_entry(params).await?;
         assert_eq!(response.to_string(), ENTRY_XML);
         mock.assert_async().await;
@@ -381,6 +1198,117 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn get_categories_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/test_user/test_blog/atom/category")
+            .with_status(200)
+            .with_body(
+                r#"<?xml version="1.0" encoding="utf-8"?>
+<service xmlns="http://www.w3.org/2007/app" xmlns:app="http://www.w3.org/2007/app" xmlns:atom="http://www.w3.org/2005/Atom">
+  <app:categories fixed="yes" scheme="http://example.com/cats">
+    <atom:category term="Perl" />
+  </app:categories>
+  <app:categories href="/test_user/test_blog/atom/category/more" />
+</service>"#,
+            )
+            .create_async()
+            .await;
+        let more_mock = server
+            .mock("GET", "/test_user/test_blog/atom/category/more")
+            .match_header(
+                "authorization",
+                mockito::Matcher::Regex("Basic .+".to_string()),
+            )
+            .with_status(200)
+            .with_body(CATEGORY_DOCUMENT_XML)
+            .create_async()
+            .await;
+        let client = Client::new(&mock_config(&server.url()));
+        let documents = client.get_categories().await?;
+        let blocks = documents
+            .into_iter()
+            .map(|document| {
+                (
+                    document
+                        .categories
+                        .into_iter()
+                        .map(|category| category.term)
+                        .collect::<Vec<String>>(),
+                    document.fixed,
+                    document.scheme,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            blocks,
+            vec![
+                (
+                    vec!["Perl".to_string()],
+                    true,
+                    Some("http://example.com/cats".to_string())
+                ),
+                (vec!["Perl".to_string(), "Scala".to_string()], false, None),
+            ]
+        );
+        mock.assert_async().await;
+        more_mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_categories_with_escaped_href_with_in_memory_transport() -> anyhow::Result<()> {
+        let transport = InMemoryTransport::new(|request| {
+            let body = if request.url.ends_with("/atom/category") {
+                r#"<?xml version="1.0" encoding="utf-8"?>
+<service xmlns="http://www.w3.org/2007/app" xmlns:app="http://www.w3.org/2007/app">
+  <app:categories href="/test_user/test_blog/atom/category/more?a=1&amp;b=2" />
+</service>"#
+            } else {
+                CATEGORY_DOCUMENT_XML
+            };
+            Ok(HttpResponse::new(StatusCode::OK, body))
+        });
+        let client = Client::builder(&mock_config("https://blog.hatena.ne.jp"))
+            .transport(transport.clone())
+            .build()?;
+        client.get_categories().await?;
+        assert_eq!(
+            transport.requests()[1].url,
+            "https://blog.hatena.ne.jp/test_user/test_blog/atom/category/more?a=1&b=2"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_categories_with_foreign_href_with_in_memory_transport() -> anyhow::Result<()> {
+        let transport = InMemoryTransport::new(|_| {
+            Ok(HttpResponse::new(
+                StatusCode::OK,
+                r#"<?xml version="1.0" encoding="utf-8"?>
+<service xmlns="http://www.w3.org/2007/app" xmlns:app="http://www.w3.org/2007/app">
+  <app:categories href="https://example.com/test_user/test_blog/atom/category" />
+</service>"#,
+            ))
+        });
+        let client = Client::builder(&mock_config("https://blog.hatena.ne.jp"))
+            .transport(transport.clone())
+            .build()?;
+        let error = client.get_categories().await.unwrap_err();
+        let ClientError::ParseCategoryError(error) = error else {
+            anyhow::bail!("unexpected error: {error:?}");
+        };
+        assert!(matches!(
+            error.kind(),
+            ParseCategoryErrorKind::OutOfLine { href }
+                if href == "https://example.com/test_user/test_blog/atom/category"
+        ));
+        // the foreign href is not requested with the credentials
+        assert_eq!(transport.requests().len(), 1);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn list_categories_with_mock() -> anyhow::Result<()> {
         let mut server = mockito::Server::new_async().await;
@@ -435,7 +1363,9 @@ mod test {
             .create_async()
             .await;
         let client = Client::new(&mock_config(&server.url()));
-        let response = client.list_entries_in_page(Some("1377584217")).await?;
+        let response = client
+            .list_entries_in_page(Some(&"1377584217".parse::<PageCursor>()?))
+            .await?;
         assert_eq!(response.to_string(), FEED_XML);
         mock.assert_async().await;
         Ok(())
@@ -456,32 +1386,137 @@ mod test {
             .await;
         let client = Client::new(&mock_config(&server.url()));
         let entry_id = "2500000000".parse::<EntryId>()?;
+        let params = entry_params();
+        let response = client.update_entry(&entry_id, params).await?;
+        assert_eq!(response.to_string(), ENTRY_XML);
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_entry_with_wsse_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/test_user/test_blog/atom/entry/2500000000")
+            .match_header("authorization", mockito::Matcher::Missing)
+            .match_header(
+                "x-wsse",
+                mockito::Matcher::Regex(
+                    r#"^UsernameToken Username="test_user", PasswordDigest="[A-Za-z0-9+/]{27}=", Nonce="[A-Za-z0-9+/]{27}=", Created="\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}Z"$"#
+                        .to_string(),
+                ),
+            )
+            .with_status(200)
+            .with_body(ENTRY_XML)
+            .create_async()
+            .await;
+        let config = mock_config(&server.url()).with_auth_mode(AuthMode::Wsse);
+        let client = Client::new(&config);
+        let entry_id = "2500000000".parse::<EntryId>()?;
+        let response = client.get_entry(&entry_id).await?;
+        assert_eq!(response.to_string(), ENTRY_XML);
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn list_entries_in_page_with_oauth_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/test_user/test_blog/atom/entry?page=1377584217")
+            .match_header(
+                "authorization",
+                mockito::Matcher::Regex(
+                    r#"^OAuth oauth_consumer_key="CONSUMER_KEY", oauth_nonce="[0-9a-f]{32}", oauth_signature_method="HMAC-SHA1", oauth_timestamp="\d+", oauth_token="TOKEN", oauth_signature="[A-Za-z0-9%]+"$"#
+                        .to_string(),
+                ),
+            )
+            .with_status(200)
+            .with_body(FEED_XML)
+            .create_async()
+            .await;
+        let config = Config::new_with_oauth(
+            "test_user",
+            Some(&server.url()),
+            "test_blog",
+            OAuthCredentials::new("CONSUMER_KEY", "CONSUMER_SECRET", "TOKEN", "TOKEN_SECRET"),
+        );
+        let client = Client::new(&config);
+        let response = client
+            .list_entries_in_page(Some(&"1377584217".parse::<PageCursor>()?))
+            .await?;
+        assert_eq!(response.to_string(), FEED_XML);
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_entry_unauthorized_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/test_user/test_blog/atom/entry/2500000000")
+            .with_status(401)
+            .create_async()
+            .await;
+        let client = Client::new(&mock_config(&server.url()));
+        let entry_id = "2500000000".parse::<EntryId>()?;
+        let result = client.get_entry(&entry_id).await;
+        assert!(matches!(result, Err(ClientError::Unauthorized(_))));
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn create_entry_bad_request_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/test_user/test_blog/atom/entry")
+            .with_status(400)
+            .with_body("Invalid XML")
+            .create_async()
+            .await;
+        let client = Client::new(&mock_config(&server.url()));
         let params = EntryParams::new(
             "test_user".to_string(),
             "記事タイトル".to_string(),
             "** 記事本文".to_string(),
             "2013-09-02T11:28:23+09:00".to_string(),
-            vec!["Scala".to_string()],
-            false,
+            vec![],
+            EntryStatus::Published,
         );
-        let response = client.update_entry(&entry_id, params).await?;
-        assert_eq!(response.to_string(), ENTRY_XML);
+        let error = client.create_entry(params).await.unwrap_err();
+        assert!(matches!(error, ClientError::BadRequest(_)));
+        let http_error = error.http_error().unwrap();
+        assert_eq!(http_error.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(http_error.method(), Method::POST);
+        assert_eq!(
+            http_error.url(),
+            format!("{}/test_user/test_blog/atom/entry", server.url())
+        );
+        assert_eq!(http_error.body(), "Invalid XML");
+        assert!(!error.is_retryable());
         mock.assert_async().await;
         Ok(())
     }
 
     #[tokio::test]
-    async fn get_entry_unauthorized_with_mock() -> anyhow::Result<()> {
+    async fn get_entry_too_many_requests_with_mock() -> anyhow::Result<()> {
         let mut server = mockito::Server::new_async().await;
         let mock = server
             .mock("GET", "/test_user/test_blog/atom/entry/2500000000")
-            .with_status(401)
+            .with_status(429)
+            .with_header("retry-after", "120")
             .create_async()
             .await;
         let client = Client::new(&mock_config(&server.url()));
         let entry_id = "2500000000".parse::<EntryId>()?;
-        let result = client.get_entry(&entry_id).await;
-        assert!(matches!(result, Err(ClientError::Unauthorized)));
+        let error = client.get_entry(&entry_id).await.unwrap_err();
+        assert!(matches!(error, ClientError::TooManyRequests(_)));
+        assert_eq!(
+            error.http_error().and_then(HttpError::retry_after),
+            Some(std::time::Duration::from_secs(120))
+        );
+        assert!(error.is_retryable());
         mock.assert_async().await;
         Ok(())
     }
@@ -497,7 +1532,7 @@ mod test {
         let client = Client::new(&mock_config(&server.url()));
         let entry_id = "2500000000".parse::<EntryId>()?;
         let result = client.get_entry(&entry_id).await;
-        assert!(matches!(result, Err(ClientError::NotFound)));
+        assert!(matches!(result, Err(ClientError::NotFound(_))));
         mock.assert_async().await;
         Ok(())
     }
@@ -513,7 +1548,160 @@ mod test {
         let client = Client::new(&mock_config(&server.url()));
         let entry_id = "2500000000".parse::<EntryId>()?;
         let result = client.get_entry(&entry_id).await;
-        assert!(matches!(result, Err(ClientError::InternalServerError)));
+        assert!(matches!(result, Err(ClientError::InternalServerError(_))));
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    fn retry_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: std::time::Duration::from_millis(1),
+            max_backoff: std::time::Duration::from_millis(1),
+            max_retry_after: std::time::Duration::from_secs(60),
+            retry_post: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn get_entry_retry_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let unavailable = server
+            .mock("GET", "/test_user/test_blog/atom/entry/2500000000")
+            .with_status(503)
+            .with_header("retry-after", "0")
+            .expect(2)
+            .create_async()
+            .await;
+        let ok = server
+            .mock("GET", "/test_user/test_blog/atom/entry/2500000000")
+            .with_status(200)
+            .with_body(ENTRY_XML)
+            .expect(1)
+            .create_async()
+            .await;
+        let client = Client::builder(&mock_config(&server.url()))
+            .retry_policy(retry_policy())
+            .build()?;
+        let entry_id = "2500000000".parse::<EntryId>()?;
+        let response = client.get_entry(&entry_id).await?;
+        assert_eq!(response.to_string(), ENTRY_XML);
+        unavailable.assert_async().await;
+        ok.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn get_entry_retry_after_too_long_with_in_memory_transport() -> anyhow::Result<()> {
+        let transport = InMemoryTransport::new(|_| {
+            let mut response = HttpResponse::new(StatusCode::SERVICE_UNAVAILABLE, "");
+            response
+                .headers
+                .insert(RETRY_AFTER, HeaderValue::from_static("86400"));
+            Ok(response)
+        });
+        let client = Client::builder(&mock_config("https://blog.hatena.ne.jp"))
+            .transport(transport.clone())
+            .retry_policy(retry_policy())
+            .build()?;
+        let entry_id = "2500000000".parse::<EntryId>()?;
+        let start = tokio::time::Instant::now();
+        let error = client.get_entry(&entry_id).await.unwrap_err();
+        assert!(matches!(error, ClientError::ServiceUnavailable(_)));
+        assert_eq!(
+            error.http_error().and_then(HttpError::retry_after),
+            Some(std::time::Duration::from_secs(86400))
+        );
+        assert_eq!(start.elapsed(), std::time::Duration::ZERO);
+        assert_eq!(transport.requests().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_entry_retry_max_attempts_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/test_user/test_blog/atom/entry/2500000000")
+            .with_status(500)
+            .expect(3)
+            .create_async()
+            .await;
+        let client = Client::builder(&mock_config(&server.url()))
+            .retry_policy(retry_policy())
+            .build()?;
+        let entry_id = "2500000000".parse::<EntryId>()?;
+        let result = client.get_entry(&entry_id).await;
+        assert!(matches!(result, Err(ClientError::InternalServerError(_))));
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn create_entry_retry_with_mock() -> anyhow::Result<()> {
+        let params = || {
+            EntryParams::new(
+                "test_user".to_string(),
+                "記事タイトル".to_string(),
+                "** 記事本文".to_string(),
+                "2013-09-02T11:28:23+09:00".to_string(),
+                vec![],
+                EntryStatus::Published,
+            )
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/test_user/test_blog/atom/entry")
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        let client = Client::builder(&mock_config(&server.url()))
+            .retry_policy(retry_policy())
+            .build()?;
+        let result = client.create_entry(params()).await;
+        assert!(matches!(result, Err(ClientError::ServiceUnavailable(_))));
+        mock.assert_async().await;
+
+        let mock = server
+            .mock("POST", "/test_user/test_blog/atom/entry")
+            .with_status(503)
+            .expect(3)
+            .create_async()
+            .await;
+        let client = Client::builder(&mock_config(&server.url()))
+            .retry_policy(RetryPolicy {
+                retry_post: true,
+                ..retry_policy()
+            })
+            .build()?;
+        let result = client.create_entry(params()).await;
+        assert!(matches!(result, Err(ClientError::ServiceUnavailable(_))));
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn get_entry_rate_limit_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/test_user/test_blog/atom/entry/2500000000")
+            .with_status(200)
+            .with_body(ENTRY_XML)
+            .expect(3)
+            .create_async()
+            .await;
+        let client = Client::builder(&mock_config(&server.url()))
+            .rate_limiter(RateLimiter::new(1.0, 1)?)
+            .build()?;
+        let cloned = client.clone();
+        let entry_id = "2500000000".parse::<EntryId>()?;
+        let start = tokio::time::Instant::now();
+        let (r1, r2) = tokio::join!(client.get_entry(&entry_id), cloned.get_entry(&entry_id));
+        r1?;
+        r2?;
+        client.clone().get_entry(&entry_id).await?;
+        assert!(start.elapsed() >= std::time::Duration::from_secs(2));
         mock.assert_async().await;
         Ok(())
     }
@@ -0,0 +1,262 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::Client;
+use crate::ClientError;
+use crate::Config;
+use crate::Middleware;
+use crate::RateLimiter;
+use crate::ReqwestTransport;
+use crate::RetryPolicy;
+use crate::Transport;
+use crate::TransportError;
+
+/// A builder for `Client`.
+///
+/// The timeouts, user agent and proxy configure the `reqwest::Client` built by
+/// `build`. They are ignored if a `reqwest::Client` is given with `http_client`,
+/// and all of them (including `http_client`) are ignored if a `Transport` is given
+/// with `transport`.
+#[derive(Debug)]
+pub struct ClientBuilder {
+    config: Config,
+    connect_timeout: Option<Duration>,
+    http_client: Option<reqwest::Client>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    proxy: Option<reqwest::Proxy>,
+    rate_limiter: Option<RateLimiter>,
+    read_timeout: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    timeout: Option<Duration>,
+    transport: Option<Arc<dyn Transport>>,
+    user_agent: Option<String>,
+}
+
+impl ClientBuilder {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            config: config.clone(),
+            connect_timeout: None,
+            http_client: None,
+            middlewares: vec![],
+            proxy: None,
+            rate_limiter: None,
+            read_timeout: None,
+            retry_policy: None,
+            timeout: None,
+            transport: None,
+            user_agent: None,
+        }
+    }
+
+    pub fn build(self) -> Result<Client, ClientError> {
+        let transport = match (self.transport, self.http_client) {
+            (Some(transport), _) => transport,
+            (None, Some(http_client)) => Arc::new(ReqwestTransport::new(http_client)),
+            (None, None) => {
+                let mut builder = reqwest::Client::builder();
+                if let Some(connect_timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(connect_timeout);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                if let Some(read_timeout) = self.read_timeout {
+                    builder = builder.read_timeout(read_timeout);
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                let http_client = builder.build().map_err(TransportError::from)?;
+                Arc::new(ReqwestTransport::new(http_client))
+            }
+        };
+        Ok(Client::from_parts(
+            self.config,
+            transport,
+            self.middlewares,
+            self.rate_limiter,
+            self.retry_policy,
+        ))
+    }
+
+    pub fn connect_timeout(self, connect_timeout: Duration) -> Self {
+        Self {
+            connect_timeout: Some(connect_timeout),
+            ..self
+        }
+    }
+
+    /// Uses the given `reqwest::Client` (and its connection pool) instead of building a new one.
+    pub fn http_client(self, http_client: reqwest::Client) -> Self {
+        Self {
+            http_client: Some(http_client),
+            ..self
+        }
+    }
+
+    /// Adds a middleware. Middlewares run in the order they are added.
+    pub fn middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    pub fn proxy(self, proxy: reqwest::Proxy) -> Self {
+        Self {
+            proxy: Some(proxy),
+            ..self
+        }
+    }
+
+    pub fn rate_limiter(self, rate_limiter: RateLimiter) -> Self {
+        Self {
+            rate_limiter: Some(rate_limiter),
+            ..self
+        }
+    }
+
+    pub fn read_timeout(self, read_timeout: Duration) -> Self {
+        Self {
+            read_timeout: Some(read_timeout),
+            ..self
+        }
+    }
+
+    pub fn retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        Self {
+            retry_policy: Some(retry_policy),
+            ..self
+        }
+    }
+
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    pub fn transport<T: Transport + 'static>(self, transport: T) -> Self {
+        Self {
+            transport: Some(Arc::new(transport)),
+            ..self
+        }
+    }
+
+    pub fn user_agent(self, user_agent: &str) -> Self {
+        Self {
+            user_agent: Some(user_agent.to_string()),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::mock_config;
+
+    #[tokio::test]
+    async fn user_agent_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/test_user/test_blog/atom/category")
+            .match_header("user-agent", "my-tool/1.0")
+            .with_status(200)
+            .create_async()
+            .await;
+        let client = ClientBuilder::new(&mock_config(&server.url()))
+            .connect_timeout(Duration::from_secs(5))
+            .read_timeout(Duration::from_secs(10))
+            .timeout(Duration::from_secs(30))
+            .user_agent("my-tool/1.0")
+            .build()?;
+        client.list_categories().await?;
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn http_client_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/test_user/test_blog/atom/category")
+            .match_header("user-agent", "shared-pool")
+            .with_status(200)
+            .create_async()
+            .await;
+        let http_client = reqwest::Client::builder()
+            .user_agent("shared-pool")
+            .build()?;
+        let client = ClientBuilder::new(&mock_config(&server.url()))
+            .http_client(http_client)
+            .user_agent("ignored")
+            .build()?;
+        client.list_categories().await?;
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn timeout_with_mock() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/test_user/test_blog/atom/category")
+            .with_status(200)
+            .with_chunked_body(|_| {
+                std::thread::sleep(std::time::Duration::from_millis(500));
+                Ok(())
+            })
+            .create_async()
+            .await;
+        let client = ClientBuilder::new(&mock_config(&server.url()))
+            .timeout(Duration::from_millis(50))
+            .build()?;
+        let result = client.list_categories().await;
+        assert!(matches!(result, Err(ClientError::RequestError(_))));
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_timeout_with_listener() -> anyhow::Result<()> {
+        // a server that accepts the connection and never responds
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await?;
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            drop(stream);
+            std::io::Result::Ok(())
+        });
+        let client = ClientBuilder::new(&mock_config(&base_url))
+            .read_timeout(Duration::from_millis(50))
+            .build()?;
+        // fails with `Elapsed` if the read timeout is not applied
+        let result = tokio::time::timeout(Duration::from_secs(5), client.list_categories()).await?;
+        assert!(matches!(result, Err(ClientError::RequestError(_))));
+        server.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn proxy_with_mock() -> anyhow::Result<()> {
+        let mut proxy = mockito::Server::new_async().await;
+        // the request for the (unresolvable) blog host is sent to the proxy
+        let mock = proxy
+            .mock("GET", "/test_user/test_blog/atom/category")
+            .match_header("host", "blog.hatena.invalid")
+            .with_status(200)
+            .create_async()
+            .await;
+        let client = ClientBuilder::new(&mock_config("http://blog.hatena.invalid"))
+            .proxy(reqwest::Proxy::http(proxy.url())?)
+            .build()?;
+        client.list_categories().await?;
+        mock.assert_async().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+use std::time::Duration;
+
+use reqwest::Method;
+use reqwest::StatusCode;
+use thiserror::Error;
+
+use crate::ParseCategoryError;
+use crate::ParseEntryError;
+use crate::TransportError;
+
+/// The maximum length (in bytes) of the response body kept in `HttpError`.
+const MAX_BODY_LENGTH: usize = 4096;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("request error")]
+    RequestError(#[from] TransportError),
+    #[error("parse entry error")]
+    ParseEntryError(#[from] ParseEntryError),
+    #[error("parse category error")]
+    ParseCategoryError(#[from] ParseCategoryError),
+    #[error("bad request: {0}")]
+    BadRequest(HttpError),
+    #[error("unauthorized: {0}")]
+    Unauthorized(HttpError),
+    #[error("forbidden: {0}")]
+    Forbidden(HttpError),
+    #[error("not found: {0}")]
+    NotFound(HttpError),
+    #[error("method not allowed: {0}")]
+    MethodNotAllowed(HttpError),
+    #[error("conflict: {0}")]
+    Conflict(HttpError),
+    #[error("too many requests: {0}")]
+    TooManyRequests(HttpError),
+    #[error("internal server error: {0}")]
+    InternalServerError(HttpError),
+    #[error("service unavailable: {0}")]
+    ServiceUnavailable(HttpError),
+    #[error("unknown status code: {0}")]
+    UnknownStatusCode(HttpError),
+}
+
+/// A response with an unsuccessful status code.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HttpError {
+    body: String,
+    method: Method,
+    retry_after: Option<Duration>,
+    status: StatusCode,
+    url: String,
+}
+
+impl ClientError {
+    pub fn http_error(&self) -> Option<&HttpError> {
+        match self {
+            ClientError::RequestError(_)
+            | ClientError::ParseEntryError(_)
+            | ClientError::ParseCategoryError(_) => None,
+            ClientError::BadRequest(e)
+            | ClientError::Unauthorized(e)
+            | ClientError::Forbidden(e)
+            | ClientError::NotFound(e)
+            | ClientError::MethodNotAllowed(e)
+            | ClientError::Conflict(e)
+            | ClientError::TooManyRequests(e)
+            | ClientError::InternalServerError(e)
+            | ClientError::ServiceUnavailable(e)
+            | ClientError::UnknownStatusCode(e) => Some(e),
+        }
+    }
+
+    pub fn status(&self) -> Option<StatusCode> {
+        self.http_error().map(HttpError::status)
+    }
+
+    /// Returns `true` if the same request may succeed when it is sent again,
+    /// i.e. on retryable transport errors (timeouts, connection errors, ...) and
+    /// `429`, `500`, `502`, `503` and `504` responses.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClientError::RequestError(e) => e.is_retryable(),
+            _ => matches!(
+                self.status(),
+                Some(
+                    StatusCode::TOO_MANY_REQUESTS
+                        | StatusCode::INTERNAL_SERVER_ERROR
+                        | StatusCode::BAD_GATEWAY
+                        | StatusCode::SERVICE_UNAVAILABLE
+                        | StatusCode::GATEWAY_TIMEOUT
+                )
+            ),
+        }
+    }
+}
+
+impl From<HttpError> for ClientError {
+    fn from(e: HttpError) -> Self {
+        match e.status {
+            StatusCode::BAD_REQUEST => ClientError::BadRequest(e),
+            StatusCode::UNAUTHORIZED => ClientError::Unauthorized(e),
+            StatusCode::FORBIDDEN => ClientError::Forbidden(e),
+            StatusCode::NOT_FOUND => ClientError::NotFound(e),
+            StatusCode::METHOD_NOT_ALLOWED => ClientError::MethodNotAllowed(e),
+            StatusCode::CONFLICT => ClientError::Conflict(e),
+            StatusCode::TOO_MANY_REQUESTS => ClientError::TooManyRequests(e),
+            StatusCode::INTERNAL_SERVER_ERROR => ClientError::InternalServerError(e),
+            StatusCode::SERVICE_UNAVAILABLE => ClientError::ServiceUnavailable(e),
+            _ => ClientError::UnknownStatusCode(e),
+        }
+    }
+}
+
+impl HttpError {
+    pub(crate) fn new(
+        status: StatusCode,
+        method: Method,
+        url: String,
+        mut body: String,
+        retry_after: Option<Duration>,
+    ) -> Self {
+        if body.len() > MAX_BODY_LENGTH {
+            let mut end = MAX_BODY_LENGTH;
+            while !body.is_char_boundary(end) {
+                end -= 1;
+            }
+            body.truncate(end);
+        }
+        Self {
+            body,
+            method,
+            retry_after,
+            status,
+            url,
+        }
+    }
+
+    /// The response body, truncated to 4096 bytes.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// The delay requested by the `Retry-After` header of a `429` or `503` response.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl std::fmt::Display for HttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} returned {}", self.method, self.url, self.status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn http_error(status: StatusCode) -> HttpError {
+        HttpError::new(
+            status,
+            Method::POST,
+            "https://blog.hatena.ne.jp/test_user/test_blog/atom/entry".to_string(),
+            "BODY".to_string(),
+            None,
+        )
+    }
+
+    #[test]
+    fn from_http_error() {
+        for (status, retryable) in [
+            (StatusCode::BAD_REQUEST, false),
+            (StatusCode::UNAUTHORIZED, false),
+            (StatusCode::FORBIDDEN, false),
+            (StatusCode::NOT_FOUND, false),
+            (StatusCode::METHOD_NOT_ALLOWED, false),
+            (StatusCode::CONFLICT, false),
+            (StatusCode::TOO_MANY_REQUESTS, true),
+            (StatusCode::INTERNAL_SERVER_ERROR, true),
+            (StatusCode::BAD_GATEWAY, true),
+            (StatusCode::SERVICE_UNAVAILABLE, true),
+            (StatusCode::GATEWAY_TIMEOUT, true),
+            (StatusCode::IM_A_TEAPOT, false),
+        ] {
+            let error = ClientError::from(http_error(status));
+            assert_eq!(error.status(), Some(status));
+            assert_eq!(error.http_error(), Some(&http_error(status)));
+            assert_eq!(error.is_retryable(), retryable);
+        }
+        assert!(matches!(
+            ClientError::from(http_error(StatusCode::FORBIDDEN)),
+            ClientError::Forbidden(_)
+        ));
+        assert!(matches!(
+            ClientError::from(http_error(StatusCode::CONFLICT)),
+            ClientError::Conflict(_)
+        ));
+        assert!(matches!(
+            ClientError::from(http_error(StatusCode::BAD_GATEWAY)),
+            ClientError::UnknownStatusCode(_)
+        ));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            ClientError::from(http_error(StatusCode::BAD_REQUEST)).to_string(),
+            "bad request: POST https://blog.hatena.ne.jp/test_user/test_blog/atom/entry returned 400 Bad Request"
+        );
+    }
+
+    #[test]
+    fn body_is_truncated() {
+        let e = HttpError::new(
+            StatusCode::BAD_REQUEST,
+            Method::GET,
+            "URL".to_string(),
+            "あ".repeat(2000),
+            None,
+        );
+        assert_eq!(e.body(), "あ".repeat(1365));
+    }
+}
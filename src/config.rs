@@ -2,9 +2,15 @@ use std::env;
 
 use thiserror::Error;
 
+use crate::AuthMode;
+use crate::OAuthCredentials;
+
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Config {
     pub api_key: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub auth_mode: AuthMode,
     pub base_url: String,
     pub blog_id: String,
     pub hatena_id: String,
@@ -20,6 +26,7 @@ impl Config {
     pub fn new(hatena_id: &str, base_url: Option<&str>, blog_id: &str, api_key: &str) -> Self {
         Config {
             api_key: api_key.into(),
+            auth_mode: AuthMode::default(),
             base_url: base_url
                 .map(|s| s.to_string())
                 .unwrap_or_else(|| "https://blog.hatena.ne.jp".to_string()),
@@ -29,16 +36,40 @@ impl Config {
     }
 
     pub fn new_from_env() -> Result<Self, ConfigError> {
-        let api_key = env::var("HATENA_API_KEY").map_err(|_| ConfigError::InvalidVar)?;
+        let auth_mode = match env::var("HATENA_AUTH_MODE").ok().as_deref() {
+            None => AuthMode::default(),
+            Some("oauth") => AuthMode::OAuth(OAuthCredentials::new(
+                &env::var("HATENA_OAUTH_CONSUMER_KEY").map_err(|_| ConfigError::InvalidVar)?,
+                &env::var("HATENA_OAUTH_CONSUMER_SECRET").map_err(|_| ConfigError::InvalidVar)?,
+                &env::var("HATENA_OAUTH_TOKEN").map_err(|_| ConfigError::InvalidVar)?,
+                &env::var("HATENA_OAUTH_TOKEN_SECRET").map_err(|_| ConfigError::InvalidVar)?,
+            )),
+            Some(s) => s.parse::<AuthMode>().map_err(|_| ConfigError::InvalidVar)?,
+        };
+        let api_key = match auth_mode {
+            AuthMode::OAuth(_) => env::var("HATENA_API_KEY").unwrap_or_default(),
+            _ => env::var("HATENA_API_KEY").map_err(|_| ConfigError::InvalidVar)?,
+        };
         let base_url = env::var("HATENA_BLOG_BASE_URL").ok();
         let blog_id = env::var("HATENA_BLOG_ID").map_err(|_| ConfigError::InvalidVar)?;
         let hatena_id = env::var("HATENA_ID").map_err(|_| ConfigError::InvalidVar)?;
-        Ok(Config::new(
-            &hatena_id,
-            base_url.as_deref(),
-            &blog_id,
-            &api_key,
-        ))
+        Ok(
+            Config::new(&hatena_id, base_url.as_deref(), &blog_id, &api_key)
+                .with_auth_mode(auth_mode),
+        )
+    }
+
+    pub fn new_with_oauth(
+        hatena_id: &str,
+        base_url: Option<&str>,
+        blog_id: &str,
+        credentials: OAuthCredentials,
+    ) -> Self {
+        Config::new(hatena_id, base_url, blog_id, "").with_auth_mode(AuthMode::OAuth(credentials))
+    }
+
+    pub fn with_auth_mode(self, auth_mode: AuthMode) -> Self {
+        Self { auth_mode, ..self }
     }
 }
 
@@ -52,6 +83,7 @@ mod test {
             Config::new("HATENA_ID", Some("BASE_URL"), "BLOG_ID", "API_KEY"),
             Config {
                 api_key: "API_KEY".into(),
+                auth_mode: AuthMode::Basic,
                 base_url: "BASE_URL".into(),
                 blog_id: "BLOG_ID".into(),
                 hatena_id: "HATENA_ID".into(),
@@ -68,6 +100,7 @@ mod test {
         temp_env::with_vars(
             [
                 ("HATENA_API_KEY", Some(hatena_api_key)),
+                ("HATENA_AUTH_MODE", None),
                 ("HATENA_BLOG_BASE_URL", Some(hatena_blog_base_url)),
                 ("HATENA_BLOG_ID", Some(hatena_blog_id)),
                 ("HATENA_ID", Some(hatena_id)),
@@ -77,6 +110,7 @@ mod test {
                     Config::new_from_env().unwrap(),
                     Config {
                         api_key: hatena_api_key.to_string(),
+                        auth_mode: AuthMode::Basic,
                         base_url: hatena_blog_base_url.to_string(),
                         blog_id: hatena_blog_id.to_string(),
                         hatena_id: hatena_id.to_string(),
@@ -85,4 +119,84 @@ mod test {
             },
         );
     }
+
+    #[test]
+    fn config_new_from_env_with_auth_mode() {
+        temp_env::with_vars(
+            [
+                ("HATENA_API_KEY", Some("hatena_api_key1")),
+                ("HATENA_AUTH_MODE", Some("wsse")),
+                ("HATENA_BLOG_BASE_URL", None),
+                ("HATENA_BLOG_ID", Some("hatena_blog_id1")),
+                ("HATENA_ID", Some("hatena_id1")),
+            ],
+            || {
+                assert_eq!(Config::new_from_env().unwrap().auth_mode, AuthMode::Wsse);
+            },
+        );
+        temp_env::with_vars(
+            [
+                ("HATENA_API_KEY", Some("hatena_api_key1")),
+                ("HATENA_AUTH_MODE", Some("unknown")),
+                ("HATENA_BLOG_BASE_URL", None),
+                ("HATENA_BLOG_ID", Some("hatena_blog_id1")),
+                ("HATENA_ID", Some("hatena_id1")),
+            ],
+            || {
+                assert!(Config::new_from_env().is_err());
+            },
+        );
+    }
+
+    #[test]
+    fn config_new_from_env_with_oauth() {
+        temp_env::with_vars(
+            [
+                ("HATENA_API_KEY", None),
+                ("HATENA_AUTH_MODE", Some("oauth")),
+                ("HATENA_BLOG_BASE_URL", None),
+                ("HATENA_BLOG_ID", Some("hatena_blog_id1")),
+                ("HATENA_ID", Some("hatena_id1")),
+                ("HATENA_OAUTH_CONSUMER_KEY", Some("consumer_key1")),
+                ("HATENA_OAUTH_CONSUMER_SECRET", Some("consumer_secret1")),
+                ("HATENA_OAUTH_TOKEN", Some("token1")),
+                ("HATENA_OAUTH_TOKEN_SECRET", Some("token_secret1")),
+            ],
+            || {
+                assert_eq!(
+                    Config::new_from_env().unwrap(),
+                    Config::new_with_oauth(
+                        "hatena_id1",
+                        None,
+                        "hatena_blog_id1",
+                        OAuthCredentials::new(
+                            "consumer_key1",
+                            "consumer_secret1",
+                            "token1",
+                            "token_secret1"
+                        )
+                    )
+                );
+            },
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn config_serde() -> anyhow::Result<()> {
+        let config = Config::new("HATENA_ID", None, "BLOG_ID", "API_KEY");
+        let json = serde_json::to_string(&config)?;
+        assert_eq!(serde_json::from_str::<Config>(&json)?, config);
+        let config = config.with_auth_mode(AuthMode::Wsse);
+        assert_eq!(
+            serde_json::to_value(&config)?["auth_mode"],
+            serde_json::json!("wsse")
+        );
+        // auth_mode defaults to basic
+        let config = serde_json::from_str::<Config>(
+            r#"{"api_key":"API_KEY","base_url":"https://blog.hatena.ne.jp","blog_id":"BLOG_ID","hatena_id":"HATENA_ID"}"#,
+        )?;
+        assert_eq!(config.auth_mode, AuthMode::Basic);
+        Ok(())
+    }
 }
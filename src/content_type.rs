@@ -0,0 +1,113 @@
+use thiserror::Error;
+
+/// The `type` attribute of `<content>`, i.e. the syntax of the entry body.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ContentType {
+    /// `text/x-hatena-syntax`
+    HatenaSyntax,
+    /// `text/html`
+    Html,
+    /// `text/x-markdown`
+    Markdown,
+    /// `text/plain`
+    PlainText,
+    /// Any other media type.
+    Unknown(String),
+}
+
+#[derive(Debug, Eq, Error, PartialEq)]
+#[error("content type parse error")]
+pub struct ContentTypeParseError {
+    _private: (),
+}
+
+impl std::fmt::Display for ContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ContentType::HatenaSyntax => "text/x-hatena-syntax",
+            ContentType::Html => "text/html",
+            ContentType::Markdown => "text/x-markdown",
+            ContentType::PlainText => "text/plain",
+            ContentType::Unknown(s) => s.as_str(),
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl std::str::FromStr for ContentType {
+    type Err = ContentTypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "" => return Err(ContentTypeParseError { _private: () }),
+            "text/x-hatena-syntax" => ContentType::HatenaSyntax,
+            "text/html" => ContentType::Html,
+            "text/x-markdown" => ContentType::Markdown,
+            "text/plain" => ContentType::PlainText,
+            _ => ContentType::Unknown(s.to_string()),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ContentType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ContentType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_conversion_test() -> anyhow::Result<()> {
+        for (s, content_type) in [
+            ("text/x-hatena-syntax", ContentType::HatenaSyntax),
+            ("text/html", ContentType::Html),
+            ("text/x-markdown", ContentType::Markdown),
+            ("text/plain", ContentType::PlainText),
+            (
+                "text/x-unknown",
+                ContentType::Unknown("text/x-unknown".to_string()),
+            ),
+        ] {
+            assert_eq!(s.parse::<ContentType>()?, content_type);
+            assert_eq!(content_type.to_string(), s);
+        }
+        assert_eq!(
+            "".parse::<ContentType>(),
+            Err(ContentTypeParseError { _private: () })
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() -> anyhow::Result<()> {
+        for (s, content_type) in [
+            (r#""text/x-hatena-syntax""#, ContentType::HatenaSyntax),
+            (r#""text/html""#, ContentType::Html),
+            (r#""text/x-markdown""#, ContentType::Markdown),
+            (r#""text/plain""#, ContentType::PlainText),
+            (
+                r#""text/x-unknown""#,
+                ContentType::Unknown("text/x-unknown".to_string()),
+            ),
+        ] {
+            assert_eq!(serde_json::to_string(&content_type)?, s);
+            assert_eq!(serde_json::from_str::<ContentType>(s)?, content_type);
+        }
+        assert!(serde_json::from_str::<ContentType>(r#""""#).is_err());
+        Ok(())
+    }
+}
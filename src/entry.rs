@@ -1,17 +1,62 @@
+use crate::ContentType;
 use crate::EntryId;
+use crate::EntryStatus;
 use crate::FixedDateTime;
 
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Entry {
     pub author_name: String,
     pub categories: Vec<String>,
     pub content: String,
-    pub draft: bool,
+    /// The `type` attribute of `<content>`.
+    pub content_type: Option<ContentType>,
+    /// The custom URL path (`<hatenablog:custom-url>`).
+    pub custom_url: Option<String>,
     pub edit_url: String,
     pub edited: FixedDateTime,
+    /// The HTML rendered by Hatena Blog (`<hatena:formatted-content>`).
+    pub formatted_content: Option<String>,
     pub id: EntryId,
     pub published: FixedDateTime,
+    pub status: EntryStatus,
+    pub summary: Option<String>,
     pub title: String,
     pub updated: FixedDateTime,
     pub url: String,
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_test() -> anyhow::Result<()> {
+        let entry = Entry {
+            author_name: "test_user".to_string(),
+            categories: vec!["Scala".to_string()],
+            content: "** 記事本文".to_string(),
+            content_type: Some(ContentType::HatenaSyntax),
+            custom_url: Some("2013/09/02/a".to_string()),
+            edit_url: "https://blog.hatena.ne.jp/test_user/test_blog/atom/entry/2500000000"
+                .to_string(),
+            edited: "2013-09-02T11:28:23+09:00".parse()?,
+            formatted_content: Some("<h4>記事本文</h4>".to_string()),
+            id: "2500000000".parse()?,
+            published: "2013-09-02T11:28:23+09:00".parse()?,
+            status: EntryStatus::Scheduled,
+            summary: None,
+            title: "記事タイトル".to_string(),
+            updated: "2013-09-02T11:28:23+09:00".parse()?,
+            url: "http://test_blog.hatenablog.com/entry/2013/09/02/a".to_string(),
+        };
+        let json = serde_json::to_value(&entry)?;
+        assert_eq!(json["content_type"], "text/x-hatena-syntax");
+        assert_eq!(json["edited"], "2013-09-02T11:28:23+09:00");
+        assert_eq!(json["id"], "2500000000");
+        assert_eq!(json["status"], "scheduled");
+        assert_eq!(json["summary"], serde_json::Value::Null);
+        assert_eq!(serde_json::from_value::<Entry>(json)?, entry);
+        Ok(())
+    }
+}
@@ -26,6 +26,22 @@ impl std::str::FromStr for EntryId {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for EntryId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for EntryId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -47,4 +63,17 @@ mod tests {
             Err(EntryIdParseError { _private: () })
         );
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() -> anyhow::Result<()> {
+        let entry_id = EntryId("2500000000".to_string());
+        assert_eq!(serde_json::to_string(&entry_id)?, r#""2500000000""#);
+        assert_eq!(
+            serde_json::from_str::<EntryId>(r#""2500000000""#)?,
+            entry_id
+        );
+        assert!(serde_json::from_str::<EntryId>(r#""""#).is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,12 @@
+use crate::BlogFeed;
+use crate::Entry;
+use crate::PageCursor;
+
+/// A collection page: the feed metadata, the entries and the cursor of the next page.
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct EntryPage {
+    pub blog: BlogFeed,
+    pub entries: Vec<Entry>,
+    pub next_page: Option<PageCursor>,
+}
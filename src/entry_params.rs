@@ -1,11 +1,32 @@
+use std::borrow::Cow;
+use std::io;
+
+use quick_xml::Writer;
+use quick_xml::events::BytesDecl;
+use quick_xml::events::BytesText;
+use quick_xml::events::Event;
+use thiserror::Error;
+
+use crate::ContentType;
+use crate::Entry;
+use crate::EntryStatus;
+use crate::FixedDateTime;
+
+const APP_NS: &str = "http://www.w3.org/2007/app";
+const ATOM_NS: &str = "http://www.w3.org/2005/Atom";
+const HATENABLOG_NS: &str = "http://www.hatena.ne.jp/info/xmlns#hatenablog";
+
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct EntryParams {
     author_name: String,
     title: String,
     content: String,
+    content_type: Option<ContentType>,
+    custom_url: Option<String>,
     updated: String, // YYYY-MM-DDTHH:MM:SS or RFC 3339 date-time
     categories: Vec<String>,
-    draft: bool,
+    status: EntryStatus,
 }
 
 impl EntryParams {
@@ -15,78 +36,170 @@ impl EntryParams {
         content: String,
         updated: String, // YYYY-MM-DDTHH:MM:SS or RFC 3339 date-time
         categories: Vec<String>,
-        draft: bool,
+        status: EntryStatus,
     ) -> Self {
         Self {
             author_name,
             title,
             content,
+            content_type: None,
+            custom_url: None,
             updated,
             categories,
-            draft,
+            status,
+        }
+    }
+
+    /// Sets the `type` attribute of `<content>`. `text/plain` is sent if it is not set.
+    ///
+    /// Use `EntryParams::try_from(&entry)` when updating an entry to keep its syntax.
+    pub fn with_content_type(self, content_type: ContentType) -> Self {
+        Self {
+            content_type: Some(content_type),
+            ..self
+        }
+    }
+
+    /// Sets the custom URL path (`<hatenablog:custom-url>`) of the entry.
+    pub fn with_custom_url(self, custom_url: String) -> Self {
+        Self {
+            custom_url: Some(custom_url),
+            ..self
+        }
+    }
+
+    /// Schedules the entry to be published at `published`, which should be in the future.
+    ///
+    /// This overrides `updated` and sets the status to `EntryStatus::Scheduled`:
+    /// Hatena Blog publishes a scheduled entry at its `updated` time.
+    ///
+    /// `published` is not checked against the current time, because the clock of the
+    /// client may differ from the server's. A time in the past is sent as is and
+    /// Hatena Blog decides how to handle it.
+    pub fn with_scheduled(self, published: FixedDateTime) -> Self {
+        Self {
+            updated: published.to_string(),
+            status: EntryStatus::Scheduled,
+            ..self
         }
     }
 
+    /// Returns the Atom entry document sent to Hatena Blog.
+    ///
+    /// Characters that are not allowed in XML 1.0 are removed from the text and attribute values.
     pub fn into_xml(self) -> String {
-        fn escape(t: &mut String, s: String) {
-            for c in s.chars() {
-                match c {
-                    '"' => t.push_str("&quot;"),
-                    '&' => t.push_str("&amp;"),
-                    '\'' => t.push_str("&apos;"),
-                    '<' => t.push_str("&lt;"),
-                    '>' => t.push_str("&gt;"),
-                    _ => t.push(c),
+        let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+        // writing to a Vec<u8> never fails
+        self.write_xml(&mut writer)
+            .expect("failed to write EntryParams XML");
+        String::from_utf8(writer.into_inner()).expect("EntryParams XML is not UTF-8")
+    }
+
+    fn write_xml(self, writer: &mut Writer<Vec<u8>>) -> io::Result<()> {
+        let content_type = self
+            .content_type
+            .unwrap_or(ContentType::PlainText)
+            .to_string();
+        let draft = match self.status {
+            EntryStatus::Published => "no",
+            EntryStatus::Draft | EntryStatus::Scheduled | EntryStatus::Unknown => "yes",
+        };
+
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("utf-8"), None)))?;
+        writer
+            .create_element("entry")
+            .with_attribute(("xmlns", ATOM_NS))
+            .with_attribute(("xmlns:app", APP_NS))
+            .with_attribute(("xmlns:hatenablog", HATENABLOG_NS))
+            .write_inner_content(|writer| {
+                write_text_element(writer, "title", &self.title)?;
+                writer
+                    .create_element("author")
+                    .write_inner_content(|writer| {
+                        write_text_element(writer, "name", &self.author_name)
+                    })?;
+                writer
+                    .create_element("content")
+                    .with_attribute(("type", sanitize(&content_type).as_ref()))
+                    .write_text_content(BytesText::new(&sanitize(&self.content)))?;
+                write_text_element(writer, "updated", &self.updated)?;
+                for category in self.categories.iter() {
+                    writer
+                        .create_element("category")
+                        .with_attribute(("term", sanitize(category).as_ref()))
+                        .write_empty()?;
                 }
-            }
-        }
+                if let Some(custom_url) = self.custom_url.as_deref() {
+                    write_text_element(writer, "hatenablog:custom-url", custom_url)?;
+                }
+                writer
+                    .create_element("app:control")
+                    .write_inner_content(|writer| {
+                        write_text_element(writer, "app:draft", draft)?;
+                        if self.status == EntryStatus::Scheduled {
+                            write_text_element(writer, "hatenablog:scheduled", "yes")?;
+                        }
+                        Ok(())
+                    })?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+}
+
+/// The status of an `Entry` is `EntryStatus::Unknown`, so it cannot be sent as is.
+#[derive(Debug, Eq, Error, PartialEq)]
+#[error("unknown entry status")]
+pub struct UnknownEntryStatusError {
+    _private: (),
+}
 
-        let mut s = String::new();
-        s.push_str(r#"<?xml version="1.0" encoding="utf-8"?>"#);
-        s.push('\n');
-        s.push_str(r#"<entry xmlns="http://www.w3.org/2005/Atom""#);
-        s.push('\n');
-        s.push_str(r#"       xmlns:app="http://www.w3.org/2007/app">"#);
-        s.push('\n');
-
-        s.push_str(r#"  <title>"#);
-        escape(&mut s, self.title);
-        s.push_str(r#"</title>"#);
-        s.push('\n');
-
-        s.push_str(r#"  <author><name>"#);
-        escape(&mut s, self.author_name);
-        s.push_str(r#"</name></author>"#);
-        s.push('\n');
-
-        s.push_str(r#"  <content type="text/plain">"#);
-        escape(&mut s, self.content);
-        s.push_str(r#"</content>"#);
-        s.push('\n');
-
-        s.push_str(r#"  <updated>"#);
-        escape(&mut s, self.updated);
-        s.push_str(r#"</updated>"#);
-        s.push('\n');
-
-        for category in self.categories.into_iter() {
-            s.push_str(r#"  <category term=""#);
-            escape(&mut s, category);
-            s.push_str(r#"" />"#);
-            s.push('\n');
+/// Builds the params to update `entry` with, keeping its content type, custom URL and status.
+///
+/// Fails if the status is `EntryStatus::Unknown`. Set `Entry::status` to the status to
+/// send before converting such an entry.
+impl TryFrom<&Entry> for EntryParams {
+    type Error = UnknownEntryStatusError;
+
+    fn try_from(entry: &Entry) -> Result<Self, Self::Error> {
+        if entry.status == EntryStatus::Unknown {
+            return Err(UnknownEntryStatusError { _private: () });
         }
+        Ok(Self {
+            author_name: entry.author_name.clone(),
+            title: entry.title.clone(),
+            content: entry.content.clone(),
+            content_type: entry.content_type.clone(),
+            custom_url: entry.custom_url.clone(),
+            updated: entry.updated.to_string(),
+            categories: entry.categories.clone(),
+            status: entry.status,
+        })
+    }
+}
 
-        s.push_str(r#"  <app:control>"#);
-        s.push('\n');
-        s.push_str(r#"    <app:draft>"#);
-        s.push_str(if self.draft { "yes" } else { "no" });
-        s.push_str(r#"</app:draft>"#);
-        s.push('\n');
-        s.push_str(r#"  </app:control>"#);
-        s.push('\n');
+fn write_text_element(writer: &mut Writer<Vec<u8>>, name: &str, text: &str) -> io::Result<()> {
+    writer
+        .create_element(name)
+        .write_text_content(BytesText::new(&sanitize(text)))?;
+    Ok(())
+}
 
-        s.push_str(r#"</entry>"#);
-        s
+/// Removes the characters that are not allowed in XML 1.0 (e.g. U+0000-U+0008).
+///
+/// <https://www.w3.org/TR/xml/#charsets>
+fn sanitize(s: &str) -> Cow<'_, str> {
+    fn is_xml_char(c: char) -> bool {
+        matches!(c,
+            '\u{9}' | '\u{A}' | '\u{D}'
+            | '\u{20}'..='\u{D7FF}'
+            | '\u{E000}'..='\u{FFFD}'
+            | '\u{10000}'..='\u{10FFFF}')
+    }
+    if s.chars().all(is_xml_char) {
+        Cow::Borrowed(s)
+    } else {
+        Cow::Owned(s.chars().filter(|c| is_xml_char(*c)).collect())
     }
 }
 
@@ -101,7 +214,7 @@ mod tests {
             "CONTENT".to_string(),
             "2020-02-07T00:00:00Z".to_string(),
             vec!["CATEGORY".to_string()],
-            true,
+            EntryStatus::Draft,
         )
     }
 
@@ -111,17 +224,177 @@ mod tests {
         assert_eq!(
             entry.into_xml(),
             r#"<?xml version="1.0" encoding="utf-8"?>
-<entry xmlns="http://www.w3.org/2005/Atom"
-       xmlns:app="http://www.w3.org/2007/app">
+<entry xmlns="http://www.w3.org/2005/Atom" xmlns:app="http://www.w3.org/2007/app" xmlns:hatenablog="http://www.hatena.ne.jp/info/xmlns#hatenablog">
   <title>TITLE</title>
-  <author><name>AUTHOR_NAME</name></author>
+  <author>
+    <name>AUTHOR_NAME</name>
+  </author>
   <content type="text/plain">CONTENT</content>
   <updated>2020-02-07T00:00:00Z</updated>
-  <category term="CATEGORY" />
+  <category term="CATEGORY"/>
   <app:control>
     <app:draft>yes</app:draft>
   </app:control>
 </entry>"#
         );
     }
+
+    #[test]
+    fn into_xml_with_custom_url() {
+        let entry = new_dummy().with_custom_url("2020/02/07/a&b".to_string());
+        assert!(entry.into_xml().contains(
+            r#"  <category term="CATEGORY"/>
+  <hatenablog:custom-url>2020/02/07/a&amp;b</hatenablog:custom-url>
+  <app:control>"#
+        ));
+    }
+
+    #[test]
+    fn into_xml_with_scheduled() -> anyhow::Result<()> {
+        let entry = EntryParams::new(
+            "AUTHOR_NAME".to_string(),
+            "TITLE".to_string(),
+            "CONTENT".to_string(),
+            "2020-02-07T00:00:00Z".to_string(),
+            vec![],
+            EntryStatus::Published,
+        )
+        .with_scheduled("2030-01-02T03:04:05+09:00".parse()?);
+        assert_eq!(
+            entry.into_xml(),
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<entry xmlns="http://www.w3.org/2005/Atom" xmlns:app="http://www.w3.org/2007/app" xmlns:hatenablog="http://www.hatena.ne.jp/info/xmlns#hatenablog">
+  <title>TITLE</title>
+  <author>
+    <name>AUTHOR_NAME</name>
+  </author>
+  <content type="text/plain">CONTENT</content>
+  <updated>2030-01-02T03:04:05+09:00</updated>
+  <app:control>
+    <app:draft>yes</app:draft>
+    <hatenablog:scheduled>yes</hatenablog:scheduled>
+  </app:control>
+</entry>"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn into_xml_with_scheduled_in_the_past() -> anyhow::Result<()> {
+        let entry = new_dummy().with_scheduled("2000-01-02T03:04:05+09:00".parse()?);
+        let xml = entry.into_xml();
+        assert!(xml.contains("<updated>2000-01-02T03:04:05+09:00</updated>"));
+        assert!(xml.contains("<hatenablog:scheduled>yes</hatenablog:scheduled>"));
+        Ok(())
+    }
+
+    #[test]
+    fn into_xml_with_unknown_status() {
+        let entry = EntryParams::new(
+            "AUTHOR_NAME".to_string(),
+            "TITLE".to_string(),
+            "CONTENT".to_string(),
+            "2020-02-07T00:00:00Z".to_string(),
+            vec![],
+            EntryStatus::Unknown,
+        );
+        assert!(
+            entry
+                .into_xml()
+                .contains("<app:draft>yes</app:draft>\n  </app:control>")
+        );
+    }
+
+    #[test]
+    fn try_from_entry() -> anyhow::Result<()> {
+        let date_time = "2020-02-07T00:00:00Z".parse::<FixedDateTime>()?;
+        let mut entry = Entry {
+            author_name: "AUTHOR_NAME".to_string(),
+            categories: vec!["CATEGORY".to_string()],
+            content: "CONTENT".to_string(),
+            content_type: Some(ContentType::Markdown),
+            custom_url: Some("2020/02/07/a".to_string()),
+            edit_url: "https://blog.hatena.ne.jp/test_user/test_blog/atom/entry/2500000000"
+                .to_string(),
+            edited: date_time,
+            formatted_content: None,
+            id: "2500000000".parse()?,
+            published: date_time,
+            status: EntryStatus::Unknown,
+            summary: None,
+            title: "TITLE".to_string(),
+            updated: date_time,
+            url: "http://test_blog.hatenablog.com/entry/2020/02/07/a".to_string(),
+        };
+        // an unknown status would be sent as a draft and unpublish the entry
+        assert_eq!(
+            EntryParams::try_from(&entry),
+            Err(UnknownEntryStatusError { _private: () })
+        );
+        entry.status = EntryStatus::Published;
+        assert_eq!(
+            EntryParams::try_from(&entry)?,
+            EntryParams::new(
+                "AUTHOR_NAME".to_string(),
+                "TITLE".to_string(),
+                "CONTENT".to_string(),
+                "2020-02-07T00:00:00Z".to_string(),
+                vec!["CATEGORY".to_string()],
+                EntryStatus::Published,
+            )
+            .with_content_type(ContentType::Markdown)
+            .with_custom_url("2020/02/07/a".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn into_xml_with_content_type() {
+        let entry = new_dummy().with_content_type(ContentType::Markdown);
+        assert!(
+            entry
+                .into_xml()
+                .contains(r#"<content type="text/x-markdown">CONTENT</content>"#)
+        );
+    }
+
+    #[test]
+    fn into_xml_without_invalid_chars() {
+        let entry = EntryParams::new(
+            "AUTHOR_NAME".to_string(),
+            "TI\u{0}TLE".to_string(),
+            "CON\u{8}TENT\t\r\n<&>".to_string(),
+            "2020-02-07T00:00:00Z".to_string(),
+            vec!["CATE\u{1b}GORY\u{FFFF}".to_string()],
+            EntryStatus::Draft,
+        );
+        let xml = entry.into_xml();
+        assert!(xml.contains("<title>TITLE</title>"));
+        assert!(xml.contains("<content type=\"text/plain\">CONTENT\t\r\n&lt;&amp;&gt;</content>"));
+        assert!(xml.contains(r#"<category term="CATEGORY"/>"#));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() -> anyhow::Result<()> {
+        let entry = new_dummy()
+            .with_content_type(ContentType::Markdown)
+            .with_custom_url("2020/02/07/a".to_string());
+        let json = serde_json::json!({
+            "author_name": "AUTHOR_NAME",
+            "title": "TITLE",
+            "content": "CONTENT",
+            "content_type": "text/x-markdown",
+            "custom_url": "2020/02/07/a",
+            "updated": "2020-02-07T00:00:00Z",
+            "categories": ["CATEGORY"],
+            "status": "draft",
+        });
+        assert_eq!(serde_json::to_value(&entry)?, json);
+        assert_eq!(
+            serde_json::from_str::<EntryParams>(&serde_json::to_string(&entry)?)?,
+            entry
+        );
+        Ok(())
+    }
 }
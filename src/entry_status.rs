@@ -0,0 +1,39 @@
+/// The publication state of an entry, as given in `<app:control>`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EntryStatus {
+    /// `<app:draft>yes</app:draft>`
+    Draft,
+    /// `<app:draft>no</app:draft>`
+    Published,
+    /// `<app:draft>yes</app:draft>` and `<hatenablog:scheduled>yes</hatenablog:scheduled>`.
+    /// The entry is published at its `updated` time.
+    Scheduled,
+    /// `<app:draft>` is missing or has an unexpected value.
+    ///
+    /// `EntryParams` sends it as a draft so that an entry is never published by mistake,
+    /// and `EntryParams::try_from(&entry)` rejects it so that an entry is never
+    /// unpublished by mistake.
+    Unknown,
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_test() -> anyhow::Result<()> {
+        for (s, status) in [
+            (r#""draft""#, EntryStatus::Draft),
+            (r#""published""#, EntryStatus::Published),
+            (r#""scheduled""#, EntryStatus::Scheduled),
+            (r#""unknown""#, EntryStatus::Unknown),
+        ] {
+            assert_eq!(serde_json::to_string(&status)?, s);
+            assert_eq!(serde_json::from_str::<EntryStatus>(s)?, status);
+        }
+        assert!(serde_json::from_str::<EntryStatus>(r#""Draft""#).is_err());
+        Ok(())
+    }
+}
@@ -40,6 +40,22 @@ impl std::str::FromStr for FixedDateTime {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for FixedDateTime {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FixedDateTime {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -65,4 +81,20 @@ mod tests {
         assert_eq!(DateTime::<FixedOffset>::from(FixedDateTime::from(dt2)), dt2);
         Ok(())
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() -> anyhow::Result<()> {
+        let fixed_date_time = FixedDateTime::from_str("2021-02-03T16:17:18+09:00")?;
+        assert_eq!(
+            serde_json::to_string(&fixed_date_time)?,
+            r#""2021-02-03T16:17:18+09:00""#
+        );
+        assert_eq!(
+            serde_json::from_str::<FixedDateTime>(r#""2021-02-03T16:17:18+09:00""#)?,
+            fixed_date_time
+        );
+        assert!(serde_json::from_str::<FixedDateTime>(r#""2021-02-03""#).is_err());
+        Ok(())
+    }
 }
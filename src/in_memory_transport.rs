@@ -0,0 +1,93 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::BoxFuture;
+use crate::HttpRequest;
+use crate::HttpResponse;
+use crate::Transport;
+use crate::TransportError;
+
+type Handler = dyn Fn(&HttpRequest) -> Result<HttpResponse, TransportError> + Send + Sync;
+
+/// A `Transport` which answers requests with a handler instead of sending them.
+///
+/// Clones share the handler and the list of received requests.
+#[derive(Clone)]
+pub struct InMemoryTransport {
+    handler: Arc<Handler>,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl InMemoryTransport {
+    pub fn new<F>(handler: F) -> Self
+    where
+        F: Fn(&HttpRequest) -> Result<HttpResponse, TransportError> + Send + Sync + 'static,
+    {
+        Self {
+            handler: Arc::new(handler),
+            requests: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    /// The requests received so far, in order.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests
+            .lock()
+            .expect("in-memory transport lock poisoned")
+            .clone()
+    }
+}
+
+impl std::fmt::Debug for InMemoryTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InMemoryTransport")
+            .field("requests", &self.requests)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Transport for InMemoryTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+        let response = (self.handler)(&request);
+        self.requests
+            .lock()
+            .expect("in-memory transport lock poisoned")
+            .push(request);
+        Box::pin(async move { response })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Method;
+    use reqwest::StatusCode;
+    use reqwest::header::HeaderMap;
+
+    use super::*;
+
+    fn request(url: &str) -> HttpRequest {
+        HttpRequest {
+            method: Method::GET,
+            url: url.to_string(),
+            headers: HeaderMap::new(),
+            body: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn send() -> anyhow::Result<()> {
+        let transport = InMemoryTransport::new(|request| match request.url.as_str() {
+            "/ok" => Ok(HttpResponse::new(StatusCode::OK, "OK")),
+            _ => Err(TransportError::retryable("connection refused")),
+        });
+        let cloned = transport.clone();
+        assert_eq!(
+            cloned.send(request("/ok")).await?,
+            HttpResponse::new(StatusCode::OK, "OK")
+        );
+        let error = cloned.send(request("/ng")).await.unwrap_err();
+        assert!(error.is_retryable());
+        assert_eq!(transport.requests(), vec![request("/ok"), request("/ng")]);
+        Ok(())
+    }
+}
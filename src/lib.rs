@@ -1,21 +1,73 @@
+mod auth_mode;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod blog_feed;
+#[cfg(feature = "cassette")]
+mod cassette;
+mod category;
+mod category_document;
 mod client;
+mod client_builder;
+mod client_error;
 mod config;
+mod content_type;
 mod entry;
 mod entry_id;
+mod entry_page;
 mod entry_params;
+mod entry_status;
 mod fixed_date_time;
+mod in_memory_transport;
+mod list_entries_limits;
+mod middleware;
+mod oauth_credentials;
+mod page_cursor;
+mod rate_limiter;
+mod reqwest_transport;
 mod response;
+mod retry_policy;
+#[cfg(test)]
+mod test_fixtures;
+mod transport;
 
+pub use self::auth_mode::AuthMode;
+pub use self::auth_mode::AuthModeParseError;
+pub use self::blog_feed::BlogFeed;
+#[cfg(feature = "cassette")]
+pub use self::cassette::CassetteError;
+#[cfg(feature = "cassette")]
+pub use self::cassette::RecordingTransport;
+#[cfg(feature = "cassette")]
+pub use self::cassette::ReplayingTransport;
+pub use self::category::Category;
+pub use self::category_document::CategoryDocument;
 pub use self::client::Client;
-pub use self::client::ClientError;
+pub use self::client_builder::ClientBuilder;
+pub use self::client_error::ClientError;
+pub use self::client_error::HttpError;
 pub use self::config::Config;
 pub use self::config::ConfigError;
+pub use self::content_type::ContentType;
+pub use self::content_type::ContentTypeParseError;
 pub use self::entry::Entry;
 pub use self::entry_id::EntryId;
 pub use self::entry_id::EntryIdParseError;
+pub use self::entry_page::EntryPage;
 pub use self::entry_params::EntryParams;
+pub use self::entry_params::UnknownEntryStatusError;
+pub use self::entry_status::EntryStatus;
 pub use self::fixed_date_time::FixedDateTime;
 pub use self::fixed_date_time::FixedDateTimeParseError;
+pub use self::in_memory_transport::InMemoryTransport;
+pub use self::list_entries_limits::ListEntriesLimits;
+pub use self::middleware::Middleware;
+pub use self::middleware::Next;
+pub use self::oauth_credentials::OAuthCredentials;
+pub use self::page_cursor::PageCursor;
+pub use self::page_cursor::PageCursorParseError;
+pub use self::rate_limiter::RateLimiter;
+pub use self::rate_limiter::RateLimiterError;
+pub use self::reqwest_transport::ReqwestTransport;
 pub use self::response::CategoryDocumentResponse;
 pub use self::response::CollectionResponse;
 pub use self::response::CreateEntryResponse;
@@ -26,6 +78,13 @@ pub use self::response::ListCategoriesResponse;
 pub use self::response::ListEntriesResponse;
 pub use self::response::MemberResponse;
 pub use self::response::ParseCategoryError;
+pub use self::response::ParseCategoryErrorKind;
 pub use self::response::ParseEntryError;
 pub use self::response::PartialList;
 pub use self::response::UpdateEntryResponse;
+pub use self::retry_policy::RetryPolicy;
+pub use self::transport::BoxFuture;
+pub use self::transport::HttpRequest;
+pub use self::transport::HttpResponse;
+pub use self::transport::Transport;
+pub use self::transport::TransportError;
@@ -0,0 +1,50 @@
+use crate::Entry;
+use crate::FixedDateTime;
+
+/// Limits on the pages fetched and the entries yielded by `Client::list_entries`.
+///
+/// `None` means no limit.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ListEntriesLimits {
+    pub max_entries: Option<usize>,
+    pub max_pages: Option<usize>,
+    /// No page is fetched after a page whose entries were all published before this time.
+    pub stop_before_published: Option<FixedDateTime>,
+}
+
+impl ListEntriesLimits {
+    pub fn with_max_entries(self, max_entries: usize) -> Self {
+        Self {
+            max_entries: Some(max_entries),
+            ..self
+        }
+    }
+
+    pub fn with_max_pages(self, max_pages: usize) -> Self {
+        Self {
+            max_pages: Some(max_pages),
+            ..self
+        }
+    }
+
+    pub fn with_stop_before_published(self, stop_before_published: FixedDateTime) -> Self {
+        Self {
+            stop_before_published: Some(stop_before_published),
+            ..self
+        }
+    }
+
+    pub(crate) fn entries_reached(&self, entries: usize) -> bool {
+        self.max_entries.is_some_and(|max| entries >= max)
+    }
+
+    pub(crate) fn pages_reached(&self, pages: usize) -> bool {
+        self.max_pages.is_some_and(|max| pages >= max)
+    }
+
+    pub(crate) fn published_passed(&self, entries: &[Entry]) -> bool {
+        self.stop_before_published.is_some_and(|stop| {
+            !entries.is_empty() && entries.iter().all(|entry| entry.published < stop)
+        })
+    }
+}
@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use crate::BoxFuture;
+use crate::HttpRequest;
+use crate::HttpResponse;
+use crate::Transport;
+use crate::TransportError;
+
+/// Intercepts the requests sent by `Client` and their responses.
+///
+/// A middleware sees each attempt's request after the authentication header is
+/// added, and the response before its status code is mapped to a `ClientError`.
+/// It passes the (possibly modified) request on with `next.run(request)`, or
+/// short-circuits by returning a response or an error without calling `next`.
+/// Middlewares run in the order they are added to `ClientBuilder`; the first one
+/// sees the request first and the response last.
+pub trait Middleware: std::fmt::Debug + Send + Sync {
+    fn handle<'a>(
+        &'a self,
+        request: HttpRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<HttpResponse, TransportError>>;
+}
+
+/// The rest of the middleware chain, ending with the `Transport`.
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    middlewares: &'a [Arc<dyn Middleware>],
+    transport: &'a dyn Transport,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(
+        middlewares: &'a [Arc<dyn Middleware>],
+        transport: &'a dyn Transport,
+    ) -> Self {
+        Self {
+            middlewares,
+            transport,
+        }
+    }
+
+    pub fn run(self, request: HttpRequest) -> BoxFuture<'a, Result<HttpResponse, TransportError>> {
+        match self.middlewares.split_first() {
+            Some((middleware, middlewares)) => middleware.handle(
+                request,
+                Next {
+                    middlewares,
+                    transport: self.transport,
+                },
+            ),
+            None => self.transport.send(request),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use reqwest::Method;
+    use reqwest::StatusCode;
+    use reqwest::header::HeaderMap;
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+    use crate::InMemoryTransport;
+
+    #[derive(Debug)]
+    struct Log {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Middleware for Log {
+        fn handle<'a>(
+            &'a self,
+            mut request: HttpRequest,
+            next: Next<'a>,
+        ) -> BoxFuture<'a, Result<HttpResponse, TransportError>> {
+            Box::pin(async move {
+                self.log
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} request", self.name));
+                request
+                    .headers
+                    .append("x-middleware", HeaderValue::from_static(self.name));
+                let response = next.run(request).await;
+                self.log
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} response", self.name));
+                response
+            })
+        }
+    }
+
+    #[derive(Debug)]
+    struct Unavailable;
+
+    impl Middleware for Unavailable {
+        fn handle<'a>(
+            &'a self,
+            _: HttpRequest,
+            _: Next<'a>,
+        ) -> BoxFuture<'a, Result<HttpResponse, TransportError>> {
+            Box::pin(async { Ok(HttpResponse::new(StatusCode::SERVICE_UNAVAILABLE, "")) })
+        }
+    }
+
+    fn request() -> HttpRequest {
+        HttpRequest {
+            method: Method::GET,
+            url: "https://blog.hatena.ne.jp/".to_string(),
+            headers: HeaderMap::new(),
+            body: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn run() -> anyhow::Result<()> {
+        let log = Arc::new(Mutex::new(vec![]));
+        let transport = InMemoryTransport::new(|_| Ok(HttpResponse::new(StatusCode::OK, "OK")));
+        let middlewares: Vec<Arc<dyn Middleware>> = vec![
+            Arc::new(Log {
+                name: "first",
+                log: log.clone(),
+            }),
+            Arc::new(Log {
+                name: "second",
+                log: log.clone(),
+            }),
+        ];
+        let response = Next::new(&middlewares, &transport).run(request()).await?;
+        assert_eq!(response, HttpResponse::new(StatusCode::OK, "OK"));
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "first request",
+                "second request",
+                "second response",
+                "first response"
+            ]
+        );
+        assert_eq!(
+            transport.requests()[0]
+                .headers
+                .get_all("x-middleware")
+                .iter()
+                .collect::<Vec<_>>(),
+            vec!["first", "second"]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn run_short_circuit() -> anyhow::Result<()> {
+        let log = Arc::new(Mutex::new(vec![]));
+        let transport = InMemoryTransport::new(|_| Ok(HttpResponse::new(StatusCode::OK, "OK")));
+        let middlewares: Vec<Arc<dyn Middleware>> = vec![
+            Arc::new(Unavailable),
+            Arc::new(Log {
+                name: "second",
+                log: log.clone(),
+            }),
+        ];
+        let response = Next::new(&middlewares, &transport).run(request()).await?;
+        assert_eq!(response.status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(log.lock().unwrap().is_empty());
+        assert!(transport.requests().is_empty());
+        Ok(())
+    }
+}
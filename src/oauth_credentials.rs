@@ -0,0 +1,166 @@
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use hmac::Hmac;
+use hmac::Mac as _;
+use reqwest::Url;
+use sha1::Sha1;
+
+/// OAuth 1.0a consumer key and access token used to sign requests on behalf of a user.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct OAuthCredentials {
+    pub consumer_key: String,
+    pub consumer_secret: String,
+    pub token: String,
+    pub token_secret: String,
+}
+
+impl OAuthCredentials {
+    pub fn new(consumer_key: &str, consumer_secret: &str, token: &str, token_secret: &str) -> Self {
+        Self {
+            consumer_key: consumer_key.into(),
+            consumer_secret: consumer_secret.into(),
+            token: token.into(),
+            token_secret: token_secret.into(),
+        }
+    }
+
+    // RFC 5849 3.5.1. Authorization Header (HMAC-SHA1)
+    pub(crate) fn authorization_header(
+        &self,
+        method: &str,
+        url: &Url,
+        nonce: &str,
+        timestamp: i64,
+    ) -> String {
+        let timestamp = timestamp.to_string();
+        let oauth_params = [
+            ("oauth_consumer_key", self.consumer_key.as_str()),
+            ("oauth_nonce", nonce),
+            ("oauth_signature_method", "HMAC-SHA1"),
+            ("oauth_timestamp", timestamp.as_str()),
+            ("oauth_token", self.token.as_str()),
+        ];
+        let signature = self.signature(method, url, &oauth_params);
+        let mut header = "OAuth ".to_string();
+        for (name, value) in oauth_params
+            .iter()
+            .copied()
+            .chain(std::iter::once(("oauth_signature", signature.as_str())))
+        {
+            if !header.ends_with(' ') {
+                header.push_str(", ");
+            }
+            header.push_str(&format!(r#"{}="{}""#, encode(name), encode(value)));
+        }
+        header
+    }
+
+    // RFC 5849 3.4.2. HMAC-SHA1
+    fn signature(&self, method: &str, url: &Url, oauth_params: &[(&str, &str)]) -> String {
+        let key = format!(
+            "{}&{}",
+            encode(&self.consumer_secret),
+            encode(&self.token_secret)
+        );
+        let mut mac = Hmac::<Sha1>::new_from_slice(key.as_bytes()).expect("any key length");
+        mac.update(signature_base_string(method, url, oauth_params).as_bytes());
+        STANDARD.encode(mac.finalize().into_bytes())
+    }
+}
+
+// RFC 5849 3.4.1. Signature Base String
+fn signature_base_string(method: &str, url: &Url, oauth_params: &[(&str, &str)]) -> String {
+    let base_string_uri = format!(
+        "{}://{}{}{}",
+        url.scheme(),
+        url.host_str().unwrap_or_default(),
+        url.port()
+            .map(|port| format!(":{}", port))
+            .unwrap_or_default(),
+        url.path()
+    );
+    let mut params = url
+        .query_pairs()
+        .map(|(name, value)| (encode(&name), encode(&value)))
+        .chain(
+            oauth_params
+                .iter()
+                .map(|(name, value)| (encode(name), encode(value))),
+        )
+        .collect::<Vec<(String, String)>>();
+    params.sort();
+    let normalized_params = params
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<String>>()
+        .join("&");
+    format!(
+        "{}&{}&{}",
+        method.to_ascii_uppercase(),
+        encode(&base_string_uri),
+        encode(&normalized_params)
+    )
+}
+
+// RFC 5849 3.6. Percent Encoding (unreserved characters are kept as is)
+fn encode(s: &str) -> String {
+    urlencoding::encode(s).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc5849_example() -> anyhow::Result<()> {
+        // RFC 5849 1.2. Example
+        let credentials = OAuthCredentials::new(
+            "dpf43f3p2l4k3l03",
+            "kd94hf93k423kf44",
+            "nnch734d00sl2jdk",
+            "pfkkdhi9sl3r4s00",
+        );
+        let url = Url::parse("http://photos.example.net/photos?file=vacation.jpg&size=original")?;
+        assert_eq!(
+            credentials.authorization_header("GET", &url, "chapoH", 137131202),
+            r#"OAuth oauth_consumer_key="dpf43f3p2l4k3l03", oauth_nonce="chapoH", oauth_signature_method="HMAC-SHA1", oauth_timestamp="137131202", oauth_token="nnch734d00sl2jdk", oauth_signature="MdpQcU8iPSUjWoN%2FUDMsK2sui9I%3D""#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn signature_base_string_test() -> anyhow::Result<()> {
+        let url =
+            Url::parse("https://blog.hatena.ne.jp/test_user/test_blog/atom/entry?page=1377584217")?;
+        assert_eq!(
+            signature_base_string(
+                "get",
+                &url,
+                &[
+                    ("oauth_consumer_key", "CONSUMER_KEY"),
+                    ("oauth_nonce", "NONCE"),
+                    ("oauth_signature_method", "HMAC-SHA1"),
+                    ("oauth_timestamp", "1377584217"),
+                    ("oauth_token", "TOKEN"),
+                ]
+            ),
+            "GET&https%3A%2F%2Fblog.hatena.ne.jp%2Ftest_user%2Ftest_blog%2Fatom%2Fentry&oauth_consumer_key%3DCONSUMER_KEY%26oauth_nonce%3DNONCE%26oauth_signature_method%3DHMAC-SHA1%26oauth_timestamp%3D1377584217%26oauth_token%3DTOKEN%26page%3D1377584217"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn collection_uri_with_page() -> anyhow::Result<()> {
+        let credentials =
+            OAuthCredentials::new("CONSUMER_KEY", "CONSUMER_SECRET", "TOKEN", "TOKEN_SECRET");
+        let url =
+            Url::parse("https://blog.hatena.ne.jp/test_user/test_blog/atom/entry?page=1377584217")?;
+        assert!(
+            credentials
+                .authorization_header("GET", &url, "NONCE", 1377584217)
+                .ends_with(r#"oauth_signature="1AfEw880Io8O0rJZ0ZsgIfUGEgw%3D""#)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use chrono::DateTime;
+use chrono::Utc;
+use thiserror::Error;
+
+/// The `page` token of a collection page, as given in the `rel="next"` link.
+///
+/// Hatena Blog uses the Unix time (in seconds) of the entries of the page as
+/// the token. A cursor can be stored as a string (or with serde, with the
+/// `serde` feature) and passed to `Client::list_entries_in_page` later.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PageCursor(String);
+
+#[derive(Debug, Eq, Error, PartialEq)]
+#[error("page cursor parse error")]
+pub struct PageCursorParseError {
+    _private: (),
+}
+
+impl PageCursor {
+    /// Returns the cursor of the page which starts at `timestamp`.
+    pub fn from_timestamp(timestamp: DateTime<Utc>) -> Self {
+        Self(timestamp.timestamp().to_string())
+    }
+
+    /// Returns the time the cursor encodes, or `None` if it is not a Unix time.
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.0
+            .parse::<i64>()
+            .ok()
+            .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+    }
+}
+
+impl std::fmt::Display for PageCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for PageCursor {
+    type Err = PageCursorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(PageCursorParseError { _private: () });
+        }
+        Ok(Self(s.to_string()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PageCursor {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PageCursor {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn string_conversion_test() {
+        assert_eq!(
+            "1377584217".parse::<PageCursor>(),
+            Ok(PageCursor("1377584217".to_string()))
+        );
+        assert_eq!(
+            PageCursor::from_str("1377584217").map(|cursor| cursor.to_string()),
+            Ok("1377584217".to_string())
+        );
+        assert_eq!(
+            "".parse::<PageCursor>(),
+            Err(PageCursorParseError { _private: () })
+        );
+    }
+
+    #[test]
+    fn timestamp_test() -> anyhow::Result<()> {
+        let timestamp = DateTime::parse_from_rfc3339("2013-08-27T06:16:57Z")?.to_utc();
+        let cursor = "1377584217".parse::<PageCursor>()?;
+        assert_eq!(cursor.timestamp(), Some(timestamp));
+        assert_eq!(PageCursor::from_timestamp(timestamp), cursor);
+        assert_eq!("abc".parse::<PageCursor>()?.timestamp(), None);
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() -> anyhow::Result<()> {
+        let cursor = "1377584217".parse::<PageCursor>()?;
+        assert_eq!(serde_json::to_string(&cursor)?, r#""1377584217""#);
+        assert_eq!(
+            serde_json::from_str::<PageCursor>(r#""1377584217""#)?,
+            cursor
+        );
+        assert!(serde_json::from_str::<PageCursor>(r#""""#).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use thiserror::Error;
+use tokio::time::Instant;
+
+/// A token bucket limiting how many requests `Client` sends per second.
+///
+/// Clones share the same bucket, so a limiter set on a `Client` applies to all of
+/// its clones and to every task using them.
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+    burst: f64,
+    requests_per_second: f64,
+}
+
+#[derive(Debug, Eq, Error, PartialEq)]
+#[error("rate limiter error: requests_per_second and burst must be positive and finite")]
+pub struct RateLimiterError {
+    _private: (),
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl RateLimiter {
+    /// Allows `requests_per_second` requests on average, and up to `burst` requests at once.
+    ///
+    /// Returns an error if `requests_per_second` is not positive and finite (or is NaN),
+    /// or if `burst` is zero.
+    pub fn new(requests_per_second: f64, burst: u32) -> Result<Self, RateLimiterError> {
+        if !(requests_per_second.is_finite() && requests_per_second > 0.0) || burst == 0 {
+            return Err(RateLimiterError { _private: () });
+        }
+        Ok(Self {
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: f64::from(burst),
+                updated_at: Instant::now(),
+            })),
+            burst: f64::from(burst),
+            requests_per_second,
+        })
+    }
+
+    /// Waits until a request may be sent.
+    ///
+    /// If the returned future is dropped while it waits (e.g. on a timeout), the
+    /// reserved token is given back to the bucket. Callers already waiting keep their
+    /// wait, but later callers do not wait for the dropped request.
+    pub(crate) async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.bucket.lock().expect("rate limiter lock poisoned");
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * self.requests_per_second).min(self.burst);
+            bucket.updated_at = now;
+            // the token is reserved immediately so that concurrent callers queue up
+            bucket.tokens -= 1.0;
+            // a tiny rate may not fit in a `Duration`, which must not panic under the lock
+            (bucket.tokens < 0.0).then(|| {
+                Duration::try_from_secs_f64(-bucket.tokens / self.requests_per_second)
+                    .unwrap_or(Duration::MAX)
+            })
+        };
+        if let Some(wait) = wait {
+            let mut reservation = Reservation {
+                bucket: &self.bucket,
+                burst: self.burst,
+                done: false,
+            };
+            tokio::time::sleep(wait).await;
+            reservation.done = true;
+        }
+    }
+}
+
+/// Gives the reserved token back if `acquire` is cancelled before it is done.
+struct Reservation<'a> {
+    bucket: &'a Mutex<Bucket>,
+    burst: f64,
+    done: bool,
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+        if let Ok(mut bucket) = self.bucket.lock() {
+            bucket.tokens = (bucket.tokens + 1.0).min(self.burst);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn acquire() -> anyhow::Result<()> {
+        let rate_limiter = RateLimiter::new(2.0, 1)?;
+        let start = Instant::now();
+        for _ in 0..5 {
+            rate_limiter.acquire().await;
+        }
+        assert_eq!(start.elapsed(), Duration::from_secs(2));
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn acquire_burst() -> anyhow::Result<()> {
+        let rate_limiter = RateLimiter::new(1.0, 3)?;
+        let start = Instant::now();
+        for _ in 0..3 {
+            rate_limiter.acquire().await;
+        }
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        rate_limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn acquire_shared_by_clones() -> anyhow::Result<()> {
+        let rate_limiter = RateLimiter::new(10.0, 1)?;
+        let start = Instant::now();
+        let tasks = (0..20)
+            .map(|_| {
+                let rate_limiter = rate_limiter.clone();
+                tokio::spawn(async move {
+                    rate_limiter.acquire().await;
+                    start.elapsed()
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut elapsed = vec![];
+        for task in tasks {
+            elapsed.push(task.await?);
+        }
+        elapsed.sort();
+        assert_eq!(elapsed.first(), Some(&Duration::ZERO));
+        assert_eq!(elapsed.last(), Some(&Duration::from_millis(1900)));
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn acquire_cancelled() -> anyhow::Result<()> {
+        let rate_limiter = RateLimiter::new(1.0, 1)?;
+        let start = Instant::now();
+        rate_limiter.acquire().await;
+        // the second request gives up before its token is available
+        assert!(
+            tokio::time::timeout(Duration::from_millis(500), rate_limiter.acquire())
+                .await
+                .is_err()
+        );
+        rate_limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn acquire_with_tiny_rate() -> anyhow::Result<()> {
+        let rate_limiter = RateLimiter::new(1e-20, 1)?;
+        rate_limiter.acquire().await;
+        // the wait does not fit in a `Duration`, and the bucket is still usable after it
+        for _ in 0..2 {
+            assert!(
+                tokio::time::timeout(Duration::from_secs(1), rate_limiter.acquire())
+                    .await
+                    .is_err()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn new_rejects_invalid_values() {
+        for (requests_per_second, burst) in [
+            (0.0, 1),
+            (-1.0, 1),
+            (f64::NAN, 1),
+            (f64::INFINITY, 1),
+            (1.0, 0),
+        ] {
+            assert_eq!(
+                RateLimiter::new(requests_per_second, burst).err(),
+                Some(RateLimiterError { _private: () })
+            );
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::BoxFuture;
+use crate::HttpRequest;
+use crate::HttpResponse;
+use crate::Transport;
+use crate::TransportError;
+
+/// The default `Transport`, which sends requests with a `reqwest::Client`.
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    http_client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(http_client: reqwest::Client) -> Self {
+        Self { http_client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+        Box::pin(async move {
+            let builder = self
+                .http_client
+                .request(request.method, request.url)
+                .headers(request.headers);
+            let builder = if let Some(body) = request.body {
+                builder.body(body)
+            } else {
+                builder
+            };
+            let response = builder.send().await?;
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = response.text().await?;
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
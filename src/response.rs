@@ -1,10 +1,20 @@
+use crate::BlogFeed;
+use crate::Category;
+use crate::CategoryDocument;
+use crate::ContentType;
 use crate::Entry;
 use crate::EntryId;
+use crate::EntryPage;
+use crate::EntryStatus;
 use crate::FixedDateTime;
+use crate::PageCursor;
 use atom_syndication::Feed;
 use quick_xml::Reader;
+use quick_xml::escape::EscapeError;
+use quick_xml::events::BytesStart;
 use quick_xml::events::Event;
-use quick_xml::events::attributes::Attributes;
+use quick_xml::events::attributes::AttrError;
+use quick_xml::name::QName;
 use reqwest::Url;
 use std::convert::TryFrom;
 use std::fmt::Display;
@@ -18,30 +28,119 @@ pub type ListCategoriesResponse = CategoryDocumentResponse;
 pub type ListEntriesResponse = CollectionResponse;
 pub type UpdateEntryResponse = MemberResponse;
 
-pub type PartialList = (Option<String>, Vec<EntryId>);
+pub type PartialList = (Option<PageCursor>, Vec<EntryId>);
 
-#[derive(Debug, Eq, Error, PartialEq)]
-#[error("parse entry error")]
-pub struct ParseEntryError {
-    _private: (),
+/// An error parsing an entry or a feed.
+///
+/// `id` is the atom `<id>` of the entry which failed, if it has one.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ParseEntryError {
+    #[error("invalid <app:edited>{}: {value}", in_entry(.id))]
+    InvalidEdited { id: Option<String>, value: String },
+    #[error("invalid <link rel=\"edit\">{}: {href}", in_entry(.id))]
+    InvalidEditLink { id: Option<String>, href: String },
+    #[error("missing <link rel=\"alternate\">{}", in_entry(.id))]
+    MissingAlternateLink { id: Option<String> },
+    #[error("missing <author>{}", in_entry(.id))]
+    MissingAuthor { id: Option<String> },
+    #[error("missing <content>{}", in_entry(.id))]
+    MissingContent { id: Option<String> },
+    #[error("missing <app:edited>{}", in_entry(.id))]
+    MissingEdited { id: Option<String> },
+    #[error("missing <link rel=\"edit\">{}", in_entry(.id))]
+    MissingEditLink { id: Option<String> },
+    #[error("missing <entry>")]
+    MissingEntry,
+    #[error("missing <published>{}", in_entry(.id))]
+    MissingPublished { id: Option<String> },
+    #[error("malformed xml")]
+    Xml(#[source] atom_syndication::Error),
 }
 
-#[derive(Debug, Eq, Error, PartialEq)]
-#[error("parse category error")]
+fn in_entry(id: &Option<String>) -> String {
+    id.as_ref()
+        .map(|id| format!(" in entry {}", id))
+        .unwrap_or_default()
+}
+
+/// An error parsing a category document, with the byte position where it was found.
+#[derive(Debug, Error)]
+#[error("{kind} at byte {position}")]
 pub struct ParseCategoryError {
-    _private: (),
+    #[source]
+    kind: ParseCategoryErrorKind,
+    position: u64,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ParseCategoryErrorKind {
+    #[error("invalid attribute")]
+    InvalidAttribute(#[source] AttrError),
+    #[error("invalid escape in {attribute} attribute")]
+    InvalidEscape {
+        attribute: &'static str,
+        #[source]
+        source: EscapeError,
+    },
+    #[error("invalid utf-8 in {attribute} attribute")]
+    InvalidUtf8 { attribute: &'static str },
+    #[error("missing <app:categories>")]
+    MissingCategories,
+    /// An out-of-line category document, which `Client::get_categories` fetches.
+    #[error("out-of-line category document {href}")]
+    OutOfLine { href: String },
+    #[error("unexpected end of document")]
+    UnexpectedEof,
+    #[error("malformed xml")]
+    Xml(#[source] quick_xml::Error),
+}
+
+impl ParseCategoryError {
+    pub fn kind(&self) -> &ParseCategoryErrorKind {
+        &self.kind
+    }
+
+    /// The byte offset in the document.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+}
+
+fn entry_error(error: ParseEntryError) -> ParseEntryError {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(error = %error, "failed to parse entry");
+    error
 }
 
-fn get_draft(entry: &atom_syndication::Entry) -> bool {
+fn category_error(kind: ParseCategoryErrorKind, position: u64) -> ParseCategoryError {
+    let error = ParseCategoryError { kind, position };
+    #[cfg(feature = "tracing")]
+    tracing::warn!(error = %error, "failed to parse category document");
+    error
+}
+
+fn get_control_value(entry: &atom_syndication::Entry, key: &str, name: &str) -> Option<String> {
     entry
         .extensions
         .get("app")
         .and_then(|e| e.get("control"))
         .and_then(|children| children.iter().find(|e| &e.name == "app:control"))
-        .and_then(|e| e.children.get("draft"))
-        .and_then(|children| children.iter().find(|e| &e.name == "app:draft"))
-        .and_then(|e| e.value.as_ref().map(|value| value == "yes"))
-        .unwrap_or(false)
+        .and_then(|e| e.children.get(key))
+        .and_then(|children| children.iter().find(|e| e.name == name))
+        .and_then(|e| e.value.clone())
+}
+
+fn get_status(entry: &atom_syndication::Entry) -> EntryStatus {
+    let draft = get_control_value(entry, "draft", "app:draft");
+    let scheduled = get_control_value(entry, "scheduled", "hatenablog:scheduled");
+    match (draft.as_deref(), scheduled.as_deref()) {
+        (Some("yes"), Some("yes")) => EntryStatus::Scheduled,
+        (Some("yes"), _) => EntryStatus::Draft,
+        (Some("no"), _) => EntryStatus::Published,
+        _ => EntryStatus::Unknown,
+    }
 }
 
 fn get_edited(entry: &atom_syndication::Entry) -> Option<String> {
@@ -53,6 +152,28 @@ fn get_edited(entry: &atom_syndication::Entry) -> Option<String> {
         .and_then(|e| e.value.clone())
 }
 
+fn get_custom_url(entry: &atom_syndication::Entry) -> Option<String> {
+    entry
+        .extensions
+        .get("hatenablog")
+        .and_then(|e| e.get("custom-url"))
+        .and_then(|children| children.iter().find(|e| &e.name == "hatenablog:custom-url"))
+        .and_then(|e| e.value.clone())
+}
+
+fn get_formatted_content(entry: &atom_syndication::Entry) -> Option<String> {
+    entry
+        .extensions
+        .get("hatena")
+        .and_then(|e| e.get("formatted-content"))
+        .and_then(|children| {
+            children
+                .iter()
+                .find(|e| &e.name == "hatena:formatted-content")
+        })
+        .and_then(|e| e.value.clone())
+}
+
 fn get_edit_url(entry: &atom_syndication::Entry) -> Option<String> {
     entry
         .links
@@ -75,16 +196,21 @@ fn get_id(entry: &atom_syndication::Entry) -> Option<EntryId> {
         .links
         .iter()
         .find(|link| link.rel == "edit")
-        .and_then(|link| link.href.split('/').last())
+        .and_then(|link| link.href.split('/').next_back())
         .and_then(|id| id.parse().ok())
 }
 
 fn to_entry(entry: atom_syndication::Entry) -> Result<Entry, ParseEntryError> {
+    let id = || (!entry.id.is_empty()).then(|| entry.id.clone());
+    let edited = get_edited(&entry)
+        .ok_or_else(|| entry_error(ParseEntryError::MissingEdited { id: id() }))?;
+    let edit_url = get_edit_url(&entry)
+        .ok_or_else(|| entry_error(ParseEntryError::MissingEditLink { id: id() }))?;
     Ok(Entry {
         author_name: entry
             .authors
             .first()
-            .ok_or(ParseEntryError { _private: () })?
+            .ok_or_else(|| entry_error(ParseEntryError::MissingAuthor { id: id() }))?
             .name
             .to_string(),
         categories: entry
@@ -94,23 +220,40 @@ fn to_entry(entry: atom_syndication::Entry) -> Result<Entry, ParseEntryError> {
             .collect::<Vec<String>>(),
         content: entry
             .content
-            .clone()
-            .ok_or(ParseEntryError { _private: () })?
-            .value
-            .ok_or(ParseEntryError { _private: () })?,
-        draft: get_draft(&entry),
-        edited: FixedDateTime::from_str(
-            get_edited(&entry)
-                .ok_or(ParseEntryError { _private: () })?
-                .as_str(),
-        )
-        .map_err(|_| ParseEntryError { _private: () })?,
-        edit_url: get_edit_url(&entry).ok_or(ParseEntryError { _private: () })?,
-        id: get_id(&entry).ok_or(ParseEntryError { _private: () })?,
-        published: FixedDateTime::from(entry.published.ok_or(ParseEntryError { _private: () })?),
+            .as_ref()
+            .and_then(|content| content.value.clone())
+            .ok_or_else(|| entry_error(ParseEntryError::MissingContent { id: id() }))?,
+        content_type: entry
+            .content
+            .as_ref()
+            .and_then(|content| content.content_type.as_deref())
+            .and_then(|content_type| ContentType::from_str(content_type).ok()),
+        custom_url: get_custom_url(&entry),
+        edited: FixedDateTime::from_str(edited.as_str()).map_err(|_| {
+            entry_error(ParseEntryError::InvalidEdited {
+                id: id(),
+                value: edited.clone(),
+            })
+        })?,
+        formatted_content: get_formatted_content(&entry),
+        id: get_id(&entry).ok_or_else(|| {
+            entry_error(ParseEntryError::InvalidEditLink {
+                id: id(),
+                href: edit_url.clone(),
+            })
+        })?,
+        edit_url,
+        published: FixedDateTime::from(
+            entry
+                .published
+                .ok_or_else(|| entry_error(ParseEntryError::MissingPublished { id: id() }))?,
+        ),
+        status: get_status(&entry),
+        summary: entry.summary.as_ref().map(|summary| summary.to_string()),
         title: entry.title.to_string(),
         updated: FixedDateTime::from(entry.updated),
-        url: get_url(&entry).ok_or(ParseEntryError { _private: () })?,
+        url: get_url(&entry)
+            .ok_or_else(|| entry_error(ParseEntryError::MissingAlternateLink { id: id() }))?,
     })
 }
 
@@ -118,7 +261,7 @@ fn first_entry(feed: &Feed) -> Result<Entry, ParseEntryError> {
     feed.entries()
         .first()
         .cloned()
-        .ok_or(ParseEntryError { _private: () })
+        .ok_or_else(|| entry_error(ParseEntryError::MissingEntry))
         .and_then(to_entry)
 }
 
@@ -128,124 +271,207 @@ fn from_entry_xml(body: &str) -> Result<Feed, ParseEntryError> {
         body.strip_prefix(r#"<?xml version="1.0" encoding="utf-8"?>"#)
             .unwrap_or(body)
     );
-    Feed::from_str(xml.as_str()).map_err(|_| ParseEntryError { _private: () })
+    Feed::from_str(xml.as_str()).map_err(|e| entry_error(ParseEntryError::Xml(e)))
 }
 
 fn from_feed_xml(body: &str) -> Result<Feed, ParseEntryError> {
-    Feed::from_str(body).map_err(|_| ParseEntryError { _private: () })
+    Feed::from_str(body).map_err(|e| entry_error(ParseEntryError::Xml(e)))
+}
+
+fn is_element(name: QName, prefix: &[u8], local_name: &[u8]) -> bool {
+    name.prefix().as_ref().map(|n| n.as_ref()) == Some(prefix)
+        && name.local_name().as_ref() == local_name
+}
+
+fn get_attribute(
+    e: &BytesStart,
+    local_name: &'static str,
+    position: u64,
+) -> Result<Option<String>, ParseCategoryError> {
+    for attr in e.attributes() {
+        let attr = attr
+            .map_err(|e| category_error(ParseCategoryErrorKind::InvalidAttribute(e), position))?;
+        if attr.key.local_name().as_ref() == local_name.as_bytes() {
+            return attr
+                .unescape_value()
+                .map(|value| Some(value.into_owned()))
+                .map_err(|e| {
+                    let kind = match e {
+                        quick_xml::Error::Escape(source) => ParseCategoryErrorKind::InvalidEscape {
+                            attribute: local_name,
+                            source,
+                        },
+                        _ => ParseCategoryErrorKind::InvalidUtf8 {
+                            attribute: local_name,
+                        },
+                    };
+                    category_error(kind, position)
+                });
+        }
+    }
+    Ok(None)
+}
+
+// the reader skips the whitespace before an event, so it is skipped here too
+fn event_position(xml: &str, reader: &Reader<&[u8]>) -> u64 {
+    let position = reader.buffer_position();
+    let rest = xml.get(position as usize..).unwrap_or_default();
+    position + (rest.len() - rest.trim_start().len()) as u64
 }
 
 fn categories_from_reader(
+    xml: &str,
     reader: &mut Reader<&[u8]>,
-    _attrs: Attributes,
-) -> Result<Vec<String>, ParseCategoryError> {
-    let mut categories = vec![];
+    scheme: Option<&str>,
+    categories: &mut Vec<Category>,
+) -> Result<(), ParseCategoryError> {
     let mut buf = vec![];
     loop {
+        let position = event_position(xml, reader);
         match reader.read_event_into(&mut buf) {
-            Ok(ns_event) => match ns_event {
-                Event::Empty(ref e)
-                    if e.name().prefix().as_ref().map(|n| n.as_ref()) == Some(b"atom")
-                        && e.name().local_name().as_ref() == b"category" =>
-                {
-                    for attr in e.attributes() {
-                        let attr = attr.map_err(|_| ParseCategoryError { _private: () })?;
-                        if attr.key.local_name().as_ref() == b"term" {
-                            let value = attr.value; // .map_err(|_| ParseCategoryError { _private: () })?;
-                            categories.push(
-                                String::from_utf8(value.to_vec())
-                                    .map_err(|_| ParseCategoryError { _private: () })?,
-                            );
-                        }
-                    }
+            Ok(Event::Empty(ref e)) | Ok(Event::Start(ref e))
+                if is_element(e.name(), b"atom", b"category") =>
+            {
+                if let Some(term) = get_attribute(e, "term", position)? {
+                    categories.push(Category {
+                        label: get_attribute(e, "label", position)?,
+                        scheme: get_attribute(e, "scheme", position)?
+                            .or_else(|| scheme.map(|s| s.to_string())),
+                        term,
+                    });
                 }
-                Event::End(ref e)
-                    if e.name().prefix().as_ref().map(|n| n.as_ref()) == Some(b"app")
-                        && e.name().local_name().as_ref() == b"categories" =>
-                {
-                    break;
-                }
-                Event::Eof => {
-                    // TODO: eof
-                    return Err(ParseCategoryError { _private: () });
-                }
-                _ => {}
-            },
-            Err(_) => {
-                // TODO: unknown
-                return Err(ParseCategoryError { _private: () });
+            }
+            Ok(Event::End(ref e)) if is_element(e.name(), b"app", b"categories") => break,
+            Ok(Event::Eof) => {
+                return Err(category_error(
+                    ParseCategoryErrorKind::UnexpectedEof,
+                    position,
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                return Err(category_error(
+                    ParseCategoryErrorKind::Xml(e),
+                    reader.error_position(),
+                ));
             }
         }
         buf.clear();
     }
-    Ok(categories)
+    Ok(())
+}
+
+/// An `<app:categories>` of a category document.
+pub(crate) enum CategoryBlock {
+    Inline(CategoryDocument),
+    /// `<app:categories href="..." />`, with the position of the element.
+    OutOfLine {
+        href: String,
+        position: u64,
+    },
 }
 
-fn from_category_document_xml(xml: &str) -> Result<Vec<String>, ParseCategoryError> {
+pub(crate) fn parse_category_document(xml: &str) -> Result<Vec<CategoryBlock>, ParseCategoryError> {
     let mut reader = Reader::from_str(xml);
     reader.config_mut().trim_text(true);
 
-    let mut categories = None;
+    let mut blocks = vec![];
     let mut buf = vec![];
     loop {
+        let position = event_position(xml, &reader);
         match reader.read_event_into(&mut buf) {
-            Ok(event) => match event {
-                Event::Start(ref e)
-                    if e.name().prefix().as_ref().map(|n| n.as_ref()) == Some(b"app")
-                        && e.name().local_name().as_ref() == b"categories" =>
-                {
-                    match categories {
-                        None => {
-                            categories = Some(categories_from_reader(&mut reader, e.attributes())?);
-                        }
-                        Some(_) => {
-                            // TODO: too many <app:categories>
-                            return Err(ParseCategoryError { _private: () });
-                        }
-                    }
+            Ok(ref event @ (Event::Start(ref e) | Event::Empty(ref e)))
+                if is_element(e.name(), b"app", b"categories") =>
+            {
+                let mut document = CategoryDocument {
+                    categories: vec![],
+                    fixed: get_attribute(e, "fixed", position)?.as_deref() == Some("yes"),
+                    scheme: get_attribute(e, "scheme", position)?,
+                };
+                let href = get_attribute(e, "href", position)?;
+                if matches!(event, Event::Start(_)) {
+                    categories_from_reader(
+                        xml,
+                        &mut reader,
+                        document.scheme.as_deref(),
+                        &mut document.categories,
+                    )?;
                 }
-                Event::Empty(ref e)
-                    if e.name().prefix().as_ref().map(|n| n.as_ref()) == Some(b"app")
-                        && e.name().local_name().as_ref() == b"categories" =>
-                {
-                    match categories {
-                        None => {
-                            // TODO: <app:categories href="{CATEGORY_DOCUMENT}" /> is not supported
-                            return Err(ParseCategoryError { _private: () });
-                        }
-                        Some(_) => {
-                            // TODO: too many <app:categories>
-                            return Err(ParseCategoryError { _private: () });
-                        }
-                    }
-                }
-                Event::Eof => break,
-                _ => {
-                    // do nothing
-                }
-            },
-            Err(_) => {
-                // TODO: unknown
-                return Err(ParseCategoryError { _private: () });
+                blocks.push(match href {
+                    Some(href) => CategoryBlock::OutOfLine { href, position },
+                    None => CategoryBlock::Inline(document),
+                });
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(e) => {
+                return Err(category_error(
+                    ParseCategoryErrorKind::Xml(e),
+                    reader.error_position(),
+                ));
             }
         }
         buf.clear();
     }
-    // TODO: no <app:categories>
-    categories.ok_or(ParseCategoryError { _private: () })
+    if blocks.is_empty() {
+        return Err(category_error(
+            ParseCategoryErrorKind::MissingCategories,
+            reader.buffer_position(),
+        ));
+    }
+    Ok(blocks)
 }
 
-fn partial_list(feed: &Feed) -> Result<(Option<String>, Vec<Entry>), ParseEntryError> {
+pub(crate) fn out_of_line_error(href: String, position: u64) -> ParseCategoryError {
+    category_error(ParseCategoryErrorKind::OutOfLine { href }, position)
+}
+
+/// Returns the inline category documents, or an `OutOfLine` error for the first
+/// out-of-line one.
+pub(crate) fn inline_category_documents(
+    blocks: Vec<CategoryBlock>,
+) -> Result<Vec<CategoryDocument>, ParseCategoryError> {
+    blocks
+        .into_iter()
+        .map(|block| match block {
+            CategoryBlock::Inline(document) => Ok(document),
+            CategoryBlock::OutOfLine { href, position } => Err(out_of_line_error(href, position)),
+        })
+        .collect()
+}
+
+fn from_category_document_xml(xml: &str) -> Result<Vec<CategoryDocument>, ParseCategoryError> {
+    inline_category_documents(parse_category_document(xml)?)
+}
+
+fn get_feed_link(feed: &Feed, rel: &str) -> Option<String> {
+    feed.links
+        .iter()
+        .find(|link| link.rel == rel)
+        .map(|link| link.href.clone())
+}
+
+fn to_blog_feed(feed: &Feed) -> BlogFeed {
+    BlogFeed {
+        author_name: feed.authors.first().map(|author| author.name.to_string()),
+        first_url: get_feed_link(feed, "first"),
+        id: feed.id.to_string(),
+        next_url: get_feed_link(feed, "next"),
+        title: feed.title.to_string(),
+        updated: FixedDateTime::from(feed.updated),
+        url: get_feed_link(feed, "alternate"),
+    }
+}
+
+fn partial_list(feed: &Feed) -> Result<(Option<PageCursor>, Vec<Entry>), ParseEntryError> {
     Ok((
-        feed.links
-            .iter()
-            .find(|link| link.rel == "next")
-            .and_then(|link| Url::parse(link.href.as_str()).ok())
+        get_feed_link(feed, "next")
+            .and_then(|href| Url::parse(href.as_str()).ok())
             .and_then(|href| {
                 href.query_pairs()
                     .into_iter()
                     .find(|(name, _)| name == "page")
-                    .map(|(_, value)| value.to_string())
+                    .and_then(|(_, value)| value.parse().ok())
             }),
         feed.entries
             .iter()
@@ -256,6 +482,8 @@ fn partial_list(feed: &Feed) -> Result<(Option<String>, Vec<Entry>), ParseEntryE
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct MemberResponse {
     body: String,
 }
@@ -288,6 +516,7 @@ impl TryFrom<MemberResponse> for Entry {
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct EmptyResponse;
 
 impl Display for EmptyResponse {
@@ -309,6 +538,8 @@ impl From<EmptyResponse> for String {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct CategoryDocumentResponse {
     body: String,
 }
@@ -331,16 +562,30 @@ impl From<CategoryDocumentResponse> for String {
     }
 }
 
-impl TryFrom<CategoryDocumentResponse> for Vec<String> {
-    type Error = ParseEntryError;
+impl TryFrom<CategoryDocumentResponse> for Vec<CategoryDocument> {
+    type Error = ParseCategoryError;
 
     fn try_from(response: CategoryDocumentResponse) -> Result<Self, Self::Error> {
         from_category_document_xml(response.body.as_str())
-            .map_err(|_| ParseEntryError { _private: () })
+    }
+}
+
+impl TryFrom<CategoryDocumentResponse> for Vec<String> {
+    type Error = ParseCategoryError;
+
+    fn try_from(response: CategoryDocumentResponse) -> Result<Self, Self::Error> {
+        let documents = from_category_document_xml(response.body.as_str())?;
+        Ok(documents
+            .into_iter()
+            .flat_map(|document| document.categories)
+            .map(|category| category.term)
+            .collect())
     }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct CollectionResponse {
     body: String,
 }
@@ -376,7 +621,7 @@ impl TryFrom<CollectionResponse> for PartialList {
     }
 }
 
-impl TryFrom<CollectionResponse> for (Option<String>, Vec<Entry>) {
+impl TryFrom<CollectionResponse> for (Option<PageCursor>, Vec<Entry>) {
     type Error = ParseEntryError;
 
     fn try_from(response: CollectionResponse) -> Result<Self, Self::Error> {
@@ -385,6 +630,20 @@ impl TryFrom<CollectionResponse> for (Option<String>, Vec<Entry>) {
     }
 }
 
+impl TryFrom<CollectionResponse> for EntryPage {
+    type Error = ParseEntryError;
+
+    fn try_from(response: CollectionResponse) -> Result<Self, Self::Error> {
+        let feed = from_feed_xml(response.body.as_str())?;
+        let (next_page, entries) = partial_list(&feed)?;
+        Ok(EntryPage {
+            blog: to_blog_feed(&feed),
+            entries,
+            next_page,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
@@ -438,23 +697,142 @@ mod tests {
     fn from_entry_xml_test() -> anyhow::Result<()> {
         let feed = from_entry_xml(GET_ENTRY_RESPONSE_XML)?;
         assert_eq!(
-            first_entry(&feed),
-            Ok(Entry {
+            first_entry(&feed)?,
+            Entry {
                 author_name: "{はてなID}".to_string(),
                 categories: vec!["Scala".to_string(), "Perl".to_string()],
                 content: "\n    ** 記事本文\n    - リスト1\n    - リスト2\n    内容\n  "
                     .to_string(),
-                draft: false,
+                content_type: Some(ContentType::HatenaSyntax),
+                custom_url: None,
                 edit_url: "https://blog.hatena.ne.jp/{はてなID}/{ブログID}/atom/edit/2500000000"
                     .to_string(),
                 edited: FixedDateTime::from_str("2013-09-02T11:28:25+09:00")?,
+                formatted_content: Some(
+                    "<div class=\"section\">\n    <h4>記事本文</h4>\n\n    <ul>\n    <li>リスト1</li>\n    <li>リスト2</li>\n    </ul><p>内容</p>\n    </div>"
+                        .to_string()
+                ),
                 id: "2500000000".parse::<EntryId>()?,
                 published: FixedDateTime::from_str("2013-09-02T11:28:24+09:00")?,
+                status: EntryStatus::Published,
+                summary: Some(" 記事本文 リスト1 リスト2 内容 ".to_string()),
                 title: "記事タイトル".to_string(),
                 updated: FixedDateTime::from_str("2013-09-02T11:28:23+09:00")?,
                 url: "http://{ブログID}/entry/2013/09/02/112823".to_string(),
-            })
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn from_entry_xml_with_custom_url_test() -> anyhow::Result<()> {
+        let xml = GET_ENTRY_RESPONSE_XML.replace(
+            "  <app:control>",
+            r#"  <hatenablog:custom-url xmlns:hatenablog="http://www.hatena.ne.jp/info/xmlns#hatenablog">2013/09/02/custom-url</hatenablog:custom-url>
+  <app:control>"#,
+        );
+        let entry = first_entry(&from_entry_xml(&xml)?)?;
+        assert_eq!(entry.custom_url, Some("2013/09/02/custom-url".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn from_entry_xml_with_scheduled_test() -> anyhow::Result<()> {
+        let xml = GET_ENTRY_RESPONSE_XML.replace(
+            "    <app:draft>no</app:draft>",
+            r#"    <app:draft>yes</app:draft>
+    <hatenablog:scheduled xmlns:hatenablog="http://www.hatena.ne.jp/info/xmlns#hatenablog">yes</hatenablog:scheduled>"#,
+        );
+        let entry = first_entry(&from_entry_xml(&xml)?)?;
+        assert_eq!(entry.status, EntryStatus::Scheduled);
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn entry_serde_test() -> anyhow::Result<()> {
+        let entry = first_entry(&from_entry_xml(GET_ENTRY_RESPONSE_XML)?)?;
+        let json = serde_json::to_value(&entry)?;
+        assert_eq!(json["id"], serde_json::json!("2500000000"));
+        assert_eq!(
+            json["edited"],
+            serde_json::json!("2013-09-02T11:28:25+09:00")
+        );
+        assert_eq!(
+            json["content_type"],
+            serde_json::json!("text/x-hatena-syntax")
         );
+        assert_eq!(json["status"], serde_json::json!("published"));
+        assert_eq!(serde_json::from_value::<Entry>(json)?, entry);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_entry_error_test() -> anyhow::Result<()> {
+        let id = Some(
+            "tag:blog.hatena.ne.jp,2013:blog-{はてなID}-20000000000000-3000000000000000"
+                .to_string(),
+        );
+        let xml = GET_ENTRY_RESPONSE_XML.replace("<author><name>{はてなID}</name></author>", "");
+        let error = first_entry(&from_entry_xml(&xml)?).unwrap_err();
+        assert!(matches!(&error, ParseEntryError::MissingAuthor { id: i } if i == &id));
+        assert_eq!(
+            error.to_string(),
+            "missing <author> in entry tag:blog.hatena.ne.jp,2013:blog-{はてなID}-20000000000000-3000000000000000"
+        );
+
+        let xml = GET_ENTRY_RESPONSE_XML.replace(
+            "<app:edited>2013-09-02T11:28:25+09:00</app:edited>",
+            "<app:edited>yesterday</app:edited>",
+        );
+        assert!(matches!(
+            first_entry(&from_entry_xml(&xml)?),
+            Err(ParseEntryError::InvalidEdited { id: i, value }) if i == id && value == "yesterday"
+        ));
+
+        let xml = GET_ENTRY_RESPONSE_XML
+            .replace(
+                "<id>tag:blog.hatena.ne.jp,2013:blog-{はてなID}-20000000000000-3000000000000000</id>",
+                "",
+            )
+            .replace("<published>2013-09-02T11:28:24+09:00</published>", "");
+        let error = first_entry(&from_entry_xml(&xml)?).unwrap_err();
+        assert!(matches!(
+            error,
+            ParseEntryError::MissingPublished { id: None }
+        ));
+        assert_eq!(error.to_string(), "missing <published>");
+
+        assert!(matches!(
+            first_entry(&from_feed_xml("<feed></feed>")?),
+            Err(ParseEntryError::MissingEntry)
+        ));
+        assert!(matches!(
+            from_feed_xml("<feed><entry>"),
+            Err(ParseEntryError::Xml(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn from_entry_xml_status_test() -> anyhow::Result<()> {
+        for (control, status) in [
+            ("<app:draft>yes</app:draft>", EntryStatus::Draft),
+            ("<app:draft>no</app:draft>", EntryStatus::Published),
+            ("<app:draft>maybe</app:draft>", EntryStatus::Unknown),
+            ("", EntryStatus::Unknown),
+        ] {
+            let xml = GET_ENTRY_RESPONSE_XML.replace("<app:draft>no</app:draft>", control);
+            let entry = first_entry(&from_entry_xml(&xml)?)?;
+            assert_eq!(entry.status, status);
+        }
+        let xml = GET_ENTRY_RESPONSE_XML.replace(
+            "  <app:control>\n    <app:draft>no</app:draft>\n  </app:control>\n",
+            "",
+        );
+        assert_ne!(xml, GET_ENTRY_RESPONSE_XML);
+        let entry = first_entry(&from_entry_xml(&xml)?)?;
+        assert_eq!(entry.status, EntryStatus::Unknown);
         Ok(())
     }
 
@@ -602,6 +980,64 @@ mod tests {
         Ok(())
     }
 
+    const LIST_ENTRIES_RESPONSE_XML: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom"
+      xmlns:app="http://www.w3.org/2007/app">
+  <link rel="first" href="https://blog.hatena.ne.jp/{はてなID}/{ブログID}/atom/entry" />
+  <link rel="next" href="https://blog.hatena.ne.jp/{はてなID}/{ブログID}/atom/entry?page=1377575606" />
+  <title>ブログタイトル</title>
+  <link rel="alternate" href="http://{ブログID}/"/>
+  <updated>2013-08-27T15:17:06+09:00</updated>
+  <author>
+    <name>{はてなID}</name>
+  </author>
+  <generator uri="http://blog.hatena.ne.jp/" version="100000000">Hatena::Blog</generator>
+  <id>hatenablog://blog/2000000000000</id>
+  <entry>
+    <id>tag:blog.hatena.ne.jp,2013:blog-{はてなID}-20000000000000-3000000000000000</id>
+    <link rel="edit" href="https://blog.hatena.ne.jp/{はてなID}/{ブログID}/atom/edit/2500000000"/>
+    <link rel="alternate" type="text/html" href="http://{ブログID}/entry/2013/09/02/112823"/>
+    <author><name>{はてなID}</name></author>
+    <title>記事タイトル</title>
+    <updated>2013-09-02T11:28:23+09:00</updated>
+    <published>2013-09-02T11:28:23+09:00</published>
+    <app:edited>2013-09-02T11:28:23+09:00</app:edited>
+    <summary type="text"> 記事本文 リスト1 リスト2 内容 </summary>
+    <content type="text/x-hatena-syntax">** 記事本文</content>
+    <category term="Scala" />
+    <app:control>
+      <app:draft>no</app:draft>
+    </app:control>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn entry_page_test() -> anyhow::Result<()> {
+        let response = CollectionResponse::from(LIST_ENTRIES_RESPONSE_XML.to_string());
+        let entry_page = EntryPage::try_from(response)?;
+        assert_eq!(
+            entry_page.blog,
+            BlogFeed {
+                author_name: Some("{はてなID}".to_string()),
+                first_url: Some(
+                    "https://blog.hatena.ne.jp/{はてなID}/{ブログID}/atom/entry".to_string()
+                ),
+                id: "hatenablog://blog/2000000000000".to_string(),
+                next_url: Some(
+                    "https://blog.hatena.ne.jp/{はてなID}/{ブログID}/atom/entry?page=1377575606"
+                        .to_string()
+                ),
+                title: "ブログタイトル".to_string(),
+                updated: FixedDateTime::from_str("2013-08-27T15:17:06+09:00")?,
+                url: Some("http://{ブログID}/".to_string()),
+            }
+        );
+        assert_eq!(entry_page.entries.len(), 1);
+        assert_eq!(entry_page.entries[0].id.to_string(), "2500000000");
+        assert_eq!(entry_page.next_page, Some("1377575606".parse()?));
+        Ok(())
+    }
+
     const CATEGORY_DOCUMENT_XML: &str = r#"<?xml version="1.0" encoding="utf-8"?>
     <app:categories
         xmlns:app="http://www.w3.org/2007/app"
@@ -613,7 +1049,8 @@ mod tests {
 
     #[test]
     fn category_document_test() -> anyhow::Result<()> {
-        let categories = from_category_document_xml(CATEGORY_DOCUMENT_XML)?;
+        let response = CategoryDocumentResponse::from(CATEGORY_DOCUMENT_XML.to_string());
+        let categories = Vec::<String>::try_from(response)?;
         assert_eq!(
             categories,
             ["Perl", "Scala"]
@@ -623,4 +1060,145 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn category_document_attributes_test() -> anyhow::Result<()> {
+        let xml = r#"<app:categories
+    xmlns:app="http://www.w3.org/2007/app"
+    xmlns:atom="http://www.w3.org/2005/Atom"
+    fixed="yes" scheme="http://example.com/cats">
+  <atom:category term="Perl" label="Perl 5" />
+  <atom:category term="Scala" scheme="http://example.com/langs" />
+</app:categories>"#;
+        assert_eq!(
+            from_category_document_xml(xml)?,
+            vec![CategoryDocument {
+                categories: vec![
+                    crate::Category {
+                        label: Some("Perl 5".to_string()),
+                        scheme: Some("http://example.com/cats".to_string()),
+                        term: "Perl".to_string(),
+                    },
+                    crate::Category {
+                        label: None,
+                        scheme: Some("http://example.com/langs".to_string()),
+                        term: "Scala".to_string(),
+                    },
+                ],
+                fixed: true,
+                scheme: Some("http://example.com/cats".to_string()),
+            }]
+        );
+        let xml = r#"<app:categories
+    xmlns:app="http://www.w3.org/2007/app"
+    xmlns:atom="http://www.w3.org/2005/Atom"
+    scheme="http://example.com/?a=1&amp;b=2">
+  <atom:category term="C&amp;C" label="R&amp;D &lt;3" />
+</app:categories>"#;
+        assert_eq!(
+            from_category_document_xml(xml)?,
+            vec![CategoryDocument {
+                categories: vec![crate::Category {
+                    label: Some("R&D <3".to_string()),
+                    scheme: Some("http://example.com/?a=1&b=2".to_string()),
+                    term: "C&C".to_string(),
+                }],
+                fixed: false,
+                scheme: Some("http://example.com/?a=1&b=2".to_string()),
+            }]
+        );
+        let documents = from_category_document_xml(CATEGORY_DOCUMENT_XML)?;
+        assert_eq!(documents.len(), 1);
+        assert!(!documents[0].fixed);
+        assert_eq!(documents[0].scheme, None);
+        Ok(())
+    }
+
+    #[test]
+    fn category_document_with_multiple_categories_test() -> anyhow::Result<()> {
+        let xml = r#"<app:service xmlns:app="http://www.w3.org/2007/app" xmlns:atom="http://www.w3.org/2005/Atom">
+  <app:categories fixed="yes" scheme="http://example.com/cats"><atom:category term="Perl" /></app:categories>
+  <app:categories />
+  <app:categories><atom:category term="Scala"></atom:category></app:categories>
+</app:service>"#;
+        let documents = from_category_document_xml(xml)?;
+        let blocks = documents
+            .iter()
+            .map(|document| {
+                (
+                    document
+                        .categories
+                        .iter()
+                        .map(|category| category.term.as_str())
+                        .collect::<Vec<&str>>(),
+                    document.fixed,
+                    document.scheme.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            blocks,
+            vec![
+                (vec!["Perl"], true, Some("http://example.com/cats")),
+                (vec![], false, None),
+                (vec!["Scala"], false, None),
+            ]
+        );
+        let response = CategoryDocumentResponse::from(xml.to_string());
+        assert_eq!(Vec::<String>::try_from(response)?, vec!["Perl", "Scala"]);
+        Ok(())
+    }
+
+    #[test]
+    fn category_document_error_test() {
+        let xml = r#"<app:service xmlns:app="http://www.w3.org/2007/app">
+  <app:categories href="https://blog.hatena.ne.jp/test_user/test_blog/atom/category/more" />
+</app:service>"#;
+        let error = from_category_document_xml(xml).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ParseCategoryErrorKind::OutOfLine { href }
+                if href == "https://blog.hatena.ne.jp/test_user/test_blog/atom/category/more"
+        ));
+        assert_eq!(error.position(), 55);
+        assert_eq!(
+            error.to_string(),
+            "out-of-line category document https://blog.hatena.ne.jp/test_user/test_blog/atom/category/more at byte 55"
+        );
+
+        let error = from_category_document_xml("<feed />").unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ParseCategoryErrorKind::MissingCategories
+        ));
+
+        let xml = "<app:categories><atom:category term=\"Perl\" />";
+        let error = from_category_document_xml(xml).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ParseCategoryErrorKind::UnexpectedEof
+        ));
+        assert_eq!(error.position(), xml.len() as u64);
+
+        let xml = "<app:categories><atom:category term=\"Perl\"></app:categories>";
+        let error = from_category_document_xml(xml).unwrap_err();
+        assert!(matches!(error.kind(), ParseCategoryErrorKind::Xml(_)));
+        assert_eq!(error.position(), 43);
+
+        let xml = r#"<app:categories href="/more?a=1&b=2" />"#;
+        let error = from_category_document_xml(xml).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ParseCategoryErrorKind::InvalidEscape {
+                attribute: "href",
+                ..
+            }
+        ));
+        assert_eq!(error.position(), 0);
+
+        assert_eq!(
+            ParseCategoryErrorKind::InvalidUtf8 { attribute: "href" }.to_string(),
+            "invalid utf-8 in href attribute"
+        );
+    }
 }
@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+use reqwest::Method;
+
+/// When and how `Client` retries a failed request.
+///
+/// Errors for which `ClientError::is_retryable` returns `true` are retried.
+/// GET, PUT and DELETE requests are retried; POST (`Client::create_entry`) is
+/// retried only if `retry_post` is set, since a retried POST may create the entry twice.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// The backoff before the second attempt. It is doubled on each retry.
+    pub initial_backoff: Duration,
+    /// The upper bound of the backoff (before jitter is applied).
+    pub max_backoff: Duration,
+    /// The longest `Retry-After` that is waited for. If the server asks for a longer
+    /// delay, the error is returned without retrying.
+    pub max_retry_after: Duration,
+    /// Whether POST requests (`Client::create_entry`) are retried too.
+    pub retry_post: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_retry_after: Duration::from_secs(60),
+            retry_post: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub(crate) fn retries_method(&self, method: &Method) -> bool {
+        match *method {
+            Method::GET | Method::PUT | Method::DELETE => true,
+            Method::POST => self.retry_post,
+            _ => false,
+        }
+    }
+
+    // Retry-After if given, the backoff otherwise. None if Retry-After is too long.
+    pub(crate) fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        match retry_after {
+            Some(retry_after) if retry_after > self.max_retry_after => None,
+            Some(retry_after) => Some(retry_after),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+
+    // exponential backoff with "equal jitter": [backoff / 2, backoff]
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_backoff);
+        let half = backoff / 2;
+        half + half.mul_f64(rand::random::<f64>())
+    }
+}
+
+// Retry-After: <delay-seconds> | <http-date>
+pub(crate) fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date_time = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date_time.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_method() {
+        let policy = RetryPolicy::default();
+        assert!(policy.retries_method(&Method::GET));
+        assert!(policy.retries_method(&Method::PUT));
+        assert!(policy.retries_method(&Method::DELETE));
+        assert!(!policy.retries_method(&Method::POST));
+        let policy = RetryPolicy {
+            retry_post: true,
+            ..RetryPolicy::default()
+        };
+        assert!(policy.retries_method(&Method::POST));
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(300),
+            ..RetryPolicy::default()
+        };
+        for (attempt, max) in [(1, 100), (2, 200), (3, 300), (4, 300), (100, 300)] {
+            let backoff = policy.backoff(attempt);
+            assert!(backoff >= Duration::from_millis(max / 2));
+            assert!(backoff <= Duration::from_millis(max));
+        }
+    }
+
+    #[test]
+    fn delay() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(100),
+            max_retry_after: Duration::from_secs(60),
+            ..RetryPolicy::default()
+        };
+        let backoff = policy.delay(1, None).unwrap();
+        assert!(backoff >= Duration::from_millis(50));
+        assert!(backoff <= Duration::from_millis(100));
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(60))),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(policy.delay(1, Some(Duration::from_secs(86400))), None);
+    }
+
+    #[test]
+    fn retry_after() -> anyhow::Result<()> {
+        let now = DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")?.with_timezone(&Utc);
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+        Ok(())
+    }
+}
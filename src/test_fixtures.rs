@@ -0,0 +1,107 @@
+//! Fixtures shared by the tests of `Client` and `blocking::Client`.
+
+use crate::Config;
+use crate::EntryParams;
+use crate::EntryStatus;
+
+pub(crate) fn mock_config(server_url: &str) -> Config {
+    Config::new("test_user", Some(server_url), "test_blog", "test_api_key")
+}
+
+pub(crate) fn entry_params() -> EntryParams {
+    EntryParams::new(
+        "test_user".to_string(),
+        "記事タイトル".to_string(),
+        "** 記事本文".to_string(),
+        "2013-09-02T11:28:23+09:00".to_string(),
+        vec!["Scala".to_string()],
+        EntryStatus::Published,
+    )
+}
+
+/// A collection page with a `next` link to `next_page` and entries of `(entry_id, edited)`.
+pub(crate) fn page_xml(next_page: Option<&str>, entries: &[(&str, &str)]) -> String {
+    let entries = entries
+        .iter()
+        .map(|(entry_id, edited)| (*entry_id, "2013-09-02T11:28:23+09:00", *edited))
+        .collect::<Vec<(&str, &str, &str)>>();
+    page_xml_with_published(next_page, &entries)
+}
+
+/// A collection page with entries of `(entry_id, published, edited)`.
+pub(crate) fn page_xml_with_published(
+    next_page: Option<&str>,
+    entries: &[(&str, &str, &str)],
+) -> String {
+    let next_link = next_page
+        .map(|page| {
+            format!(
+                r#"<link rel="next" href="https://blog.hatena.ne.jp/test_user/test_blog/atom/entry?page={page}" />"#
+            )
+        })
+        .unwrap_or_default();
+    let entries = entries
+        .iter()
+        .map(|(entry_id, published, edited)| {
+            format!(
+                r#"<entry>
+    <id>tag:blog.hatena.ne.jp,2013:blog-test_user-20000000000000-{entry_id}</id>
+    <link rel="edit" href="https://blog.hatena.ne.jp/test_user/test_blog/atom/entry/{entry_id}"/>
+    <link rel="alternate" type="text/html" href="http://test_blog.hatenablog.com/entry/{entry_id}"/>
+    <author><name>test_user</name></author>
+    <title>記事タイトル</title>
+    <updated>2013-09-02T11:28:23+09:00</updated>
+    <published>{published}</published>
+    <app:edited>{edited}</app:edited>
+    <content type="text/x-hatena-syntax">** 記事本文</content>
+  </entry>"#
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom"
+      xmlns:app="http://www.w3.org/2007/app">
+  {next_link}
+  <title>ブログタイトル</title>
+  <updated>2013-08-27T15:17:06+09:00</updated>
+  <id>hatenablog://blog/2000000000000</id>
+  {entries}
+</feed>"#
+    )
+}
+
+pub(crate) const ENTRY_XML: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<entry xmlns="http://www.w3.org/2005/Atom"
+       xmlns:app="http://www.w3.org/2007/app">
+  <id>tag:blog.hatena.ne.jp,2013:blog-test_user-20000000000000-3000000000000000</id>
+  <link rel="edit" href="https://blog.hatena.ne.jp/test_user/test_blog/atom/entry/2500000000"/>
+  <link rel="alternate" type="text/html" href="http://test_blog.hatenablog.com/entry/2013/09/02/112823"/>
+  <author><name>test_user</name></author>
+  <title>記事タイトル</title>
+  <updated>2013-09-02T11:28:23+09:00</updated>
+  <published>2013-09-02T11:28:23+09:00</published>
+  <app:edited>2013-09-02T11:28:23+09:00</app:edited>
+  <summary type="text"> 記事本文 リスト1 リスト2 内容 </summary>
+  <content type="text/x-hatena-syntax">
+** 記事本文
+- リスト1
+- リスト2
+内容
+  </content>
+  <hatena:formatted-content type="text/html" xmlns:hatena="http://www.hatena.ne.jp/info/xmlns#">
+    <div class="section">
+    <h4>記事本文</h4>
+    <ul>
+    <li>リスト1</li>
+    <li>リスト2</li>
+    </ul><p>内容</p>
+    </div>
+  </hatena:formatted-content>
+  <category term="Scala" />
+  <category term="Perl" />
+  <app:control>
+    <app:draft>no</app:draft>
+  </app:control>
+</entry>"#;
@@ -20,3 +20,210 @@ fn entry_id_rejects_empty_string() {
     use hatena_blog_api::EntryId;
     assert!("".parse::<EntryId>().is_err());
 }
+
+// BREAKING CHANGE (v0.3): `ClientError` variants for unsuccessful responses carry an
+// `HttpError` (status, method, URL and body), and `RequestError` carries a
+// `TransportError` instead of a `reqwest::Error`.
+#[tokio::test]
+async fn client_error_carries_http_error() -> anyhow::Result<()> {
+    use hatena_blog_api::Client;
+    use hatena_blog_api::ClientError;
+    use hatena_blog_api::Config;
+    use hatena_blog_api::EntryId;
+    use hatena_blog_api::HttpError;
+    use hatena_blog_api::HttpResponse;
+    use hatena_blog_api::InMemoryTransport;
+    use hatena_blog_api::TransportError;
+    use reqwest::StatusCode;
+
+    let config = Config::new(
+        "test_user",
+        Some("https://blog.hatena.ne.jp"),
+        "test_blog",
+        "test_api_key",
+    );
+    let entry_id = "2500000000".parse::<EntryId>()?;
+
+    let transport = InMemoryTransport::new(|_| Ok(HttpResponse::new(StatusCode::NOT_FOUND, "")));
+    let client = Client::builder(&config).transport(transport).build()?;
+    match client.get_entry(&entry_id).await {
+        Err(ClientError::NotFound(e)) => {
+            let e: HttpError = e;
+            assert_eq!(e.status(), StatusCode::NOT_FOUND);
+            assert_eq!(
+                e.url(),
+                "https://blog.hatena.ne.jp/test_user/test_blog/atom/entry/2500000000"
+            );
+        }
+        _ => panic!("expected NotFound"),
+    }
+
+    let transport = InMemoryTransport::new(|_| Err(TransportError::new("connection refused")));
+    let client = Client::builder(&config).transport(transport).build()?;
+    match client.get_entry(&entry_id).await {
+        Err(ClientError::RequestError(e)) => {
+            let _: TransportError = e;
+        }
+        _ => panic!("expected RequestError"),
+    }
+    Ok(())
+}
+
+// BREAKING CHANGE (v0.3): `Client::list_entries_in_page` takes an `Option<&PageCursor>`
+// instead of an `Option<&str>`, and `PartialList` returns the next page as a `PageCursor`.
+#[tokio::test]
+async fn list_entries_in_page_takes_page_cursor() -> anyhow::Result<()> {
+    use hatena_blog_api::Client;
+    use hatena_blog_api::Config;
+    use hatena_blog_api::HttpResponse;
+    use hatena_blog_api::InMemoryTransport;
+    use hatena_blog_api::PageCursor;
+    use hatena_blog_api::PartialList;
+    use reqwest::StatusCode;
+
+    let transport = InMemoryTransport::new(|_| {
+        Ok(HttpResponse::new(
+            StatusCode::OK,
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <link rel="next" href="https://blog.hatena.ne.jp/test_user/test_blog/atom/entry?page=1377584217" />
+  <title>TITLE</title>
+  <updated>2013-08-27T15:17:06+09:00</updated>
+  <id>hatenablog://blog/2000000000000</id>
+</feed>"#,
+        ))
+    });
+    let config = Config::new(
+        "test_user",
+        Some("https://blog.hatena.ne.jp"),
+        "test_blog",
+        "test_api_key",
+    );
+    let client = Client::builder(&config)
+        .transport(transport.clone())
+        .build()?;
+    let page = "1377584217".parse::<PageCursor>()?;
+    let response = client.list_entries_in_page(Some(&page)).await?;
+    let (next_page, _): PartialList = response.try_into()?;
+    assert_eq!(next_page, Some(page));
+    assert_eq!(
+        transport.requests()[0].url,
+        "https://blog.hatena.ne.jp/test_user/test_blog/atom/entry?page=1377584217"
+    );
+    Ok(())
+}
+
+// BREAKING CHANGE (v0.3): `EntryParams::new` takes an `EntryStatus` instead of
+// `draft: bool`, and `Entry::draft` has been replaced by `Entry::status`.
+#[test]
+fn entry_status_replaces_draft() {
+    use hatena_blog_api::Entry;
+    use hatena_blog_api::EntryParams;
+    use hatena_blog_api::EntryStatus;
+
+    let params = EntryParams::new(
+        "AUTHOR_NAME".to_string(),
+        "TITLE".to_string(),
+        "CONTENT".to_string(),
+        "2020-02-07T00:00:00Z".to_string(),
+        vec![],
+        EntryStatus::Draft,
+    );
+    assert!(params.into_xml().contains("<app:draft>yes</app:draft>"));
+
+    fn _is_draft(entry: &Entry) -> bool {
+        entry.status == EntryStatus::Draft
+    }
+}
+
+// BREAKING CHANGE (v0.3): `ParseEntryError` no longer implements `Eq` / `PartialEq`,
+// and it is `#[non_exhaustive]`, so a `match` on it needs a wildcard arm.
+#[test]
+fn parse_entry_error_does_not_implement_eq() {
+    use hatena_blog_api::ParseEntryError;
+    use static_assertions::assert_not_impl_any;
+    assert_not_impl_any!(ParseEntryError: Eq, PartialEq);
+
+    fn _describe_entry_error(error: &ParseEntryError) -> &'static str {
+        match error {
+            ParseEntryError::MissingEntry => "missing entry",
+            ParseEntryError::MissingContent { .. } => "missing content",
+            _ => "other",
+        }
+    }
+}
+
+// BREAKING CHANGE (v0.3): `ParseCategoryError` no longer implements `Eq` / `PartialEq`,
+// and `ParseCategoryErrorKind` is `#[non_exhaustive]`, so a `match` on it needs a
+// wildcard arm.
+#[test]
+fn parse_category_error_does_not_implement_eq() {
+    use hatena_blog_api::ParseCategoryError;
+    use hatena_blog_api::ParseCategoryErrorKind;
+    use static_assertions::assert_not_impl_any;
+    assert_not_impl_any!(ParseCategoryError: Eq, PartialEq);
+
+    fn _describe_category_error(error: &ParseCategoryError) -> &'static str {
+        match error.kind() {
+            ParseCategoryErrorKind::MissingCategories => "missing categories",
+            ParseCategoryErrorKind::UnexpectedEof => "unexpected eof",
+            _ => "other",
+        }
+    }
+}
+
+// BREAKING CHANGE (v0.3): `Config` has a new `auth_mode` field, so a struct literal
+// must set it (`AuthMode::Basic` keeps the previous behavior).
+#[test]
+fn config_has_auth_mode() {
+    use hatena_blog_api::AuthMode;
+    use hatena_blog_api::Config;
+    let config = Config {
+        api_key: "test_api_key".to_string(),
+        auth_mode: AuthMode::Basic,
+        base_url: "https://blog.hatena.ne.jp".to_string(),
+        blog_id: "test_blog".to_string(),
+        hatena_id: "test_user".to_string(),
+    };
+    assert_eq!(
+        config,
+        Config::new(
+            "test_user",
+            Some("https://blog.hatena.ne.jp"),
+            "test_blog",
+            "test_api_key"
+        )
+    );
+}
+
+// BREAKING CHANGE (v0.3): `Entry` has new fields, so a struct literal must set
+// `content_type`, `custom_url`, `formatted_content`, `status` (instead of `draft`)
+// and `summary`.
+#[test]
+fn entry_has_new_fields() -> anyhow::Result<()> {
+    use hatena_blog_api::ContentType;
+    use hatena_blog_api::Entry;
+    use hatena_blog_api::EntryStatus;
+    use hatena_blog_api::FixedDateTime;
+
+    let date_time = "2013-09-02T11:28:23+09:00".parse::<FixedDateTime>()?;
+    let entry = Entry {
+        author_name: "test_user".to_string(),
+        categories: vec![],
+        content: "CONTENT".to_string(),
+        content_type: Some(ContentType::Markdown),
+        custom_url: None,
+        edit_url: "https://blog.hatena.ne.jp/test_user/test_blog/atom/entry/2500000000".to_string(),
+        edited: date_time,
+        formatted_content: None,
+        id: "2500000000".parse()?,
+        published: date_time,
+        status: EntryStatus::Published,
+        summary: None,
+        title: "TITLE".to_string(),
+        updated: date_time,
+        url: "http://test_blog.hatenablog.com/entry/2013/09/02/112823".to_string(),
+    };
+    assert_eq!(entry.content_type, Some(ContentType::Markdown));
+    Ok(())
+}